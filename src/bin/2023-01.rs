@@ -2,7 +2,7 @@ use core::panic;
 
 use regex::Regex;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(2023, 1);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let result = input
@@ -69,7 +69,9 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples",
+            PUZZLE,
+            advent_of_code::part!(1),
         ));
         assert_eq!(result, Some(142));
     }
@@ -77,7 +79,9 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples",
+            PUZZLE,
+            advent_of_code::part!(2),
         ));
         assert_eq!(result, Some(281));
     }
@@ -1,8 +1,8 @@
 use std::str::FromStr;
 
-use itertools::Itertools;
+use advent_of_code::parse::{self, ParseError};
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2023, 2);
 
 struct Game {
     id: u32,
@@ -26,18 +26,14 @@ impl Game {
 }
 
 impl FromStr for Game {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (game_name, draws) = s.split(": ").collect_tuple().unwrap();
-        let game_id = game_name.split(' ').last().unwrap().parse().unwrap();
+        let (game_name, draws) = parse::key_value(s, ":")?;
+        let id = parse::int(parse::split_once(game_name, "Game ")?.1)?;
+        let draws = draws.split(';').map(str::parse).collect::<Result<_, _>>()?;
 
-        let draws = draws
-            .split("; ")
-            .map(|draw| draw.parse().unwrap())
-            .collect();
-
-        Ok(Game { id: game_id, draws })
+        Ok(Game { id, draws })
     }
 }
 
@@ -49,20 +45,19 @@ struct Draw {
 }
 
 impl FromStr for Draw {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut draw = Draw::default();
-        s.split(", ").for_each(|part| {
-            let (num, color) = part.split(' ').collect_tuple().unwrap();
-            let num = num.parse().unwrap();
+        for (num, color) in parse::key_values(s, ",", " ")? {
+            let num = parse::int(num)?;
             match color {
                 "red" => draw.red = num,
                 "green" => draw.green = num,
                 "blue" => draw.blue = num,
-                _ => panic!("invalid color"),
+                _ => return Err(ParseError::invalid(color, "invalid color")),
             }
-        });
+        }
         Ok(draw)
     }
 }
@@ -72,7 +67,7 @@ const LIM_GREEN: u32 = 13;
 const LIM_BLUE: u32 = 14;
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let games = input.lines().map(|game| game.parse::<Game>().unwrap());
+    let games = parse::lines(input, str::parse::<Game>).unwrap().into_iter();
 
     let valid_games = games.filter(|game| {
         game.draws
@@ -85,7 +80,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let games = input.lines().map(|game| game.parse::<Game>().unwrap());
+    let games = parse::lines(input, str::parse::<Game>).unwrap().into_iter();
 
     let result = games
         .map(|game| game.minimum_set().into_iter().product::<u32>())
@@ -99,13 +94,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2286));
     }
 }
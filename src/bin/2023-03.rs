@@ -0,0 +1,107 @@
+use advent_of_code::grid::Grid;
+use advent_of_code::prelude::*;
+
+advent_of_code::solution!(2023, 3);
+
+fn has_symbol_neighbor(grid: &Grid<char>, pos: (usize, usize)) -> bool {
+    grid.neighbors8(pos)
+        .map(|pos| grid[pos])
+        .any(|char| char != '.' && !char.is_ascii_digit())
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().unwrap();
+
+    let mut part_numbers = Vec::new();
+
+    let mut current_number = Vec::new();
+    let mut has_symbol = false;
+
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            let char = grid[(x, y)];
+            if char.is_ascii_digit() {
+                current_number.push(char);
+                if !has_symbol {
+                    has_symbol = has_symbol_neighbor(&grid, (x, y));
+                }
+            } else if !current_number.is_empty() {
+                if has_symbol {
+                    let number = current_number.iter().collect::<String>().parse().unwrap();
+                    part_numbers.push(number);
+                }
+                current_number.clear();
+                has_symbol = false;
+            }
+        }
+
+        if !current_number.is_empty() {
+            if has_symbol {
+                let number = current_number.iter().collect::<String>().parse().unwrap();
+                part_numbers.push(number);
+            }
+            current_number.clear();
+            has_symbol = false;
+        }
+    }
+
+    Some(part_numbers.iter().sum())
+}
+
+fn find_number(grid: &Grid<char>, (x, y): (usize, usize)) -> u32 {
+    let line = grid.row(y);
+    let mut first_part = line[..x]
+        .iter()
+        .rev()
+        .take_while(|char| char.is_ascii_digit())
+        .collect_vec();
+    first_part.reverse();
+    let number = [
+        first_part,
+        line[x..]
+            .iter()
+            .take_while(|char| char.is_ascii_digit())
+            .collect_vec(),
+    ]
+    .concat()
+    .into_iter()
+    .collect::<String>();
+    number.parse().unwrap()
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().unwrap();
+
+    let mut gear_ratio_sum = 0;
+
+    for gear in grid.find_all(&'*') {
+        let numbers = grid
+            .neighbors8(gear)
+            .filter(|&pos| grid[pos].is_ascii_digit())
+            .map(|pos| find_number(&grid, pos))
+            .unique()
+            .collect_vec();
+        if numbers.len() == 2 {
+            gear_ratio_sum += numbers[0] * numbers[1];
+        }
+    }
+
+    Some(gear_ratio_sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(4361));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(467835));
+    }
+}
@@ -1,11 +1,10 @@
 use std::str::FromStr;
 
-use itermore::prelude::*;
-use itertools::Itertools;
-use rayon::prelude::*;
+use advent_of_code::interval::{Interval, IntervalMap, IntervalSet};
+use advent_of_code::prelude::*;
 use regex::Regex;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2023, 5);
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 enum Category {
@@ -45,61 +44,30 @@ struct Almanac {
 struct Mapping {
     from: Category,
     to: Category,
-    ranges: Vec<MapRange>,
-}
-
-struct MapRange {
-    destination_start: u64,
-    source_start: u64,
-    length: u64,
+    map: IntervalMap<u64>,
 }
 
 impl Almanac {
-    fn map_seeds_to(&self, target: Category) -> impl ParallelIterator<Item = u64> + '_ {
-        self.seeds
-            .par_iter()
-            .map(move |seed| self.map_seed_to(*seed, target))
-    }
-
-    fn map_seed_ranges_to(&self, target: Category) -> impl ParallelIterator<Item = u64> + '_ {
-        self.seeds
-            .iter()
-            .array_chunked()
-            .par_bridge()
-            .flat_map(|[start, len]| (*start..*start + *len))
-            .map(move |seed| self.map_seed_to(seed, target))
-    }
-
-    fn map_seed_to(&self, seed: u64, target: Category) -> u64 {
-        let mut value = seed;
-        let mut index = 0;
+    /// Returns the map from seeds to the target category, composing every mapping on the way.
+    fn map_to(&self, target: Category) -> IntervalMap<u64> {
+        let mut map = IntervalMap::new();
         let mut current = self.mappings[0].from;
-        while current != target {
-            let mapping = &self.mappings[index];
-            value = mapping.map(value);
+        for mapping in &self.mappings {
+            if current == target {
+                break;
+            }
+            map = map.then(&mapping.map);
             current = mapping.to;
-            index += 1;
         }
-        value
+        map
     }
-}
 
-impl Mapping {
-    fn map(&self, value: u64) -> u64 {
-        self.ranges
+    fn seed_ranges(&self) -> IntervalSet<u64> {
+        self.seeds
             .iter()
-            .find(|r| r.includes(value))
-            .map_or(value, |r| r.apply(value))
-    }
-}
-
-impl MapRange {
-    fn includes(&self, value: u64) -> bool {
-        value >= self.source_start && value < self.source_start + self.length
-    }
-
-    fn apply(&self, value: u64) -> u64 {
-        value - self.source_start + self.destination_start
+            .tuples()
+            .map(|(&start, &len)| Interval::with_len(start, len))
+            .collect()
     }
 }
 
@@ -133,42 +101,35 @@ impl FromStr for Mapping {
         let from = captures.get(1).unwrap().as_str().parse().unwrap();
         let to = captures.get(2).unwrap().as_str().parse().unwrap();
 
-        let ranges = lines.map(|line| line.parse().unwrap()).collect_vec();
-
-        Ok(Self { from, to, ranges })
-    }
-}
-
-impl FromStr for MapRange {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (destination_start, source_start, length) = s
-            .split_whitespace()
-            .map(|n| n.parse().unwrap())
-            .collect_tuple()
-            .unwrap();
+        let map = lines
+            .map(|line| {
+                let (destination, source, length) = line
+                    .split_whitespace()
+                    .map(|n| n.parse().unwrap())
+                    .collect_tuple()
+                    .unwrap();
+                (Interval::with_len(source, length), destination)
+            })
+            .collect();
 
-        Ok(Self {
-            destination_start,
-            source_start,
-            length,
-        })
+        Ok(Self { from, to, map })
     }
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
     let almanac: Almanac = input.parse().unwrap();
-    let lowest_location = almanac.map_seeds_to(Category::Location).min();
+    let map = almanac.map_to(Category::Location);
 
-    lowest_location
+    almanac.seeds.iter().map(|&seed| map.map(seed)).min()
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
     let almanac: Almanac = input.parse().unwrap();
-    let lowest_location = almanac.map_seed_ranges_to(Category::Location).min();
+    let locations = almanac
+        .map_to(Category::Location)
+        .map_set(&almanac.seed_ranges());
 
-    lowest_location
+    locations.min()
 }
 
 #[cfg(test)]
@@ -177,13 +138,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(46));
     }
 }
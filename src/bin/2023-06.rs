@@ -1,6 +1,6 @@
-use itertools::Itertools;
+use advent_of_code::prelude::*;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2023, 6);
 
 fn parse_races_part1(input: &str) -> impl Iterator<Item = (u64, u64)> + '_ {
     let mut iter = input
@@ -43,13 +43,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(288));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(71503));
     }
 }
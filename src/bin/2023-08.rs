@@ -0,0 +1,134 @@
+use std::str::FromStr;
+
+use advent_of_code::graph::LabelledGraph;
+use advent_of_code::math;
+use advent_of_code::parse::{self, ParseError};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+advent_of_code::solution!(2023, 8);
+
+struct Map {
+    operations: Vec<Direction>,
+    network: LabelledGraph<Direction>,
+}
+
+impl Map {
+    fn step(&self, node: NodeIndex, steps: u64) -> NodeIndex {
+        let direction = self.operations[steps as usize % self.operations.len()];
+        self.network
+            .graph()
+            .edges(node)
+            .find(|edge| *edge.weight() == direction)
+            .expect("every node has a left and right neighbour")
+            .target()
+    }
+
+    fn traverse(&self, start: &str, goal: &str) -> u64 {
+        let mut current = self.network.node(start).unwrap();
+        let goal = self.network.node(goal).unwrap();
+        let mut steps = 0;
+
+        while current != goal {
+            current = self.step(current, steps);
+            steps += 1;
+        }
+
+        steps
+    }
+
+    fn traverse_ghost(&self) -> u64 {
+        let is_end = |node| self.network.label(node).ends_with('Z');
+        let steps = self
+            .network
+            .nodes()
+            .filter(|(_, label)| label.ends_with('A'))
+            .map(|(mut current, _)| {
+                let mut steps = 0;
+                while !is_end(current) {
+                    current = self.step(current, steps);
+                    steps += 1;
+                }
+                steps
+            });
+
+        math::lcm_all(steps)
+    }
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (operations, nodes) = parse::split_once(s, "\n\n")?;
+        let operations = operations
+            .trim()
+            .char_indices()
+            .map(|(i, c)| {
+                let at = &operations[i..i + c.len_utf8()];
+                parse::one_of(at, &[("L", Direction::Left), ("R", Direction::Right)])
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut network = LabelledGraph::new();
+        for (name, left, right) in parse::lines(nodes, |line| {
+            let (name, targets) = parse::key_value(line, "=")?;
+            let targets = targets
+                .strip_prefix('(')
+                .and_then(|targets| targets.strip_suffix(')'))
+                .ok_or_else(|| ParseError::invalid(targets, "expected (left, right)"))?;
+            let (left, right) = parse::key_value(targets, ",")?;
+            Ok((name, left, right))
+        })? {
+            network.add_edge(name, left, Direction::Left);
+            network.add_edge(name, right, Direction::Right);
+        }
+
+        Ok(Self {
+            operations,
+            network,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Left,
+    Right,
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let map: Map = parse::from_str(input).unwrap();
+    Some(map.traverse("AAA", "ZZZ"))
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let map: Map = parse::from_str(input).unwrap();
+    Some(map.traverse_ghost())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file_variant(
+            "examples", PUZZLE, 1,
+        ));
+        assert_eq!(result, Some(2));
+
+        let result = part_one(&advent_of_code::template::read_file_variant(
+            "examples", PUZZLE, 2,
+        ));
+        assert_eq!(result, Some(6));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file_variant(
+            "examples", PUZZLE, 3,
+        ));
+        assert_eq!(result, Some(6));
+    }
+}
@@ -1,45 +1,21 @@
-use std::collections::VecDeque;
+use advent_of_code::math;
+use advent_of_code::prelude::*;
 
-use itertools::Itertools;
+advent_of_code::solution!(2023, 9);
 
-advent_of_code::solution!(9);
-
-fn prepare_rows(seq: Vec<i64>) -> Vec<VecDeque<i64>> {
-    let mut rows: Vec<VecDeque<i64>> = Vec::new();
-    rows.push(seq.into());
-
-    let mut last_row = &rows[rows.len() - 1];
-    while last_row.iter().any(|x| *x != 0) {
-        let new_row = last_row
-            .iter()
-            .tuple_windows()
-            .map(|(a, b)| b - a)
-            .collect();
-        rows.push(new_row);
-        last_row = &rows[rows.len() - 1];
-    }
-
-    rows
+/// Evaluates the polynomial through the sequence, which is sampled at `0..seq.len()`, at `x`.
+fn extrapolate(seq: Vec<i64>, x: i64) -> i64 {
+    let points = seq.into_iter().zip(0..).map(|(y, x)| (x, y)).collect_vec();
+    math::to_i64(&math::Newton::new(&points).eval(x)).expect("sequences continue with integers")
 }
 
 fn continue_sequence_start(seq: Vec<i64>) -> i64 {
-    let mut rows = prepare_rows(seq);
-
-    for i in (0..=rows.len() - 2).rev() {
-        let next = rows[i].front().unwrap() - rows[i + 1].front().unwrap();
-        rows[i].push_front(next);
-    }
-    *rows[0].front().unwrap()
+    extrapolate(seq, -1)
 }
 
 fn continue_sequence_end(seq: Vec<i64>) -> i64 {
-    let mut rows = prepare_rows(seq);
-
-    for i in (0..=rows.len() - 2).rev() {
-        let next = rows[i].back().unwrap() + rows[i + 1].back().unwrap();
-        rows[i].push_back(next);
-    }
-    *rows[0].back().unwrap()
+    let len = seq.len() as i64;
+    extrapolate(seq, len)
 }
 
 pub fn part_one(input: &str) -> Option<i64> {
@@ -82,7 +58,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(114));
     }
 
@@ -94,7 +70,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 }
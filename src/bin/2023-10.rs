@@ -1,37 +1,33 @@
-use std::{collections::HashSet, str::FromStr};
+use std::str::FromStr;
 
-use itertools::Itertools;
-use rayon::prelude::*;
+use advent_of_code::grid::{Grid, GridParseError};
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::prelude::*;
+use advent_of_code::template::{is_tracing, viz_dir};
+use advent_of_code::viz::{Image, Rgb};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2023, 10);
 
 struct PipeGrid {
-    grid: Vec<Vec<Pipe>>,
+    grid: Grid<Pipe>,
     animal: (usize, usize),
 }
 
 impl PipeGrid {
     fn get_pipe(&self, pos: &(usize, usize)) -> &Pipe {
-        &self.grid[pos.1][pos.0]
+        &self.grid[*pos]
     }
 
     fn pipe_neighbors(&self, pos: &(usize, usize)) -> Vec<(usize, usize)> {
         self.get_pipe(pos)
             .offsets()
-            .iter()
-            .map(|(x, y)| (x + pos.0 as isize, y + pos.1 as isize))
-            .filter(|(x, y)| {
-                x >= &0
-                    && y >= &0
-                    && *x < self.grid[0].len() as isize
-                    && *y < self.grid.len() as isize
-            })
-            .map(|(x, y)| (x as usize, y as usize))
+            .into_iter()
+            .filter_map(|offset| self.grid.offset(*pos, offset))
             .collect_vec()
     }
 
     fn traverse_loop(&self, start: (usize, usize)) -> Vec<(usize, usize)> {
-        let mut visited = HashSet::new();
+        let mut visited = HashSet::default();
         let mut current = start;
         let mut path = vec![];
 
@@ -67,32 +63,7 @@ impl PipeGrid {
     }
 
     fn grid_neighbors(&self, pos: &(usize, usize)) -> Vec<(usize, usize)> {
-        let mut neighbors = vec![];
-        if pos.0 > 0 {
-            neighbors.push((pos.0 - 1, pos.1));
-            if pos.1 > 0 {
-                neighbors.push((pos.0 - 1, pos.1 - 1));
-            }
-        }
-        if pos.0 < self.grid[0].len() - 1 {
-            neighbors.push((pos.0 + 1, pos.1));
-            if pos.1 < self.grid.len() - 1 {
-                neighbors.push((pos.0 + 1, pos.1 + 1));
-            }
-        }
-        if pos.1 > 0 {
-            neighbors.push((pos.0, pos.1 - 1));
-            if pos.0 < self.grid[0].len() - 1 {
-                neighbors.push((pos.0 + 1, pos.1 - 1));
-            }
-        }
-        if pos.1 < self.grid.len() - 1 {
-            neighbors.push((pos.0, pos.1 + 1));
-            if pos.0 > 0 {
-                neighbors.push((pos.0 - 1, pos.1 + 1));
-            }
-        }
-        neighbors
+        self.grid.neighbors8(*pos).collect()
     }
 
     fn find_pocket(
@@ -100,7 +71,7 @@ impl PipeGrid {
         pipe_loop: Vec<(usize, usize)>,
         pos: &(usize, usize),
     ) -> HashSet<(usize, usize)> {
-        let mut found: HashSet<(usize, usize)> = HashSet::new();
+        let mut found: HashSet<(usize, usize)> = HashSet::default();
         let mut stack = vec![*pos];
 
         while let Some(current) = stack.pop() {
@@ -142,11 +113,7 @@ impl PipeGrid {
                             let mut y = p.1 as isize;
                             let mut count = 0;
 
-                            while x >= 0
-                                && y >= 0
-                                && x < self.grid[0].len() as isize
-                                && y < self.grid.len() as isize
-                            {
+                            while self.grid.contains((x, y)) {
                                 if pipe_loop.contains(&(x as usize, y as usize)) {
                                     count += 1;
                                 }
@@ -169,8 +136,14 @@ impl PipeGrid {
             c > pocket.len() / 2
         });
 
-        let enclosed = inner_pockets.clone().flatten().cloned().collect();
-        println!("{}", grid_to_string(&self, &pipe_loop, &enclosed));
+        let enclosed: Vec<_> = inner_pockets.clone().flatten().cloned().collect();
+        if is_tracing() {
+            println!("{}", grid_to_string(self, &pipe_loop, &enclosed));
+        }
+        if let Some(dir) = viz_dir() {
+            let path = dir.join(format!("{PUZZLE}-enclosed.png"));
+            render(self, &pipe_loop, &enclosed).save(path).unwrap();
+        }
 
         inner_pockets.map(|p| p.len() as u32).sum()
     }
@@ -192,47 +165,69 @@ fn pipe_to_char(pipe: &Pipe) -> char {
 
 fn grid_to_string(
     grid: &PipeGrid,
-    pipe_loop: &Vec<(usize, usize)>,
-    enclosed: &Vec<(usize, usize)>,
+    pipe_loop: &[(usize, usize)],
+    enclosed: &[(usize, usize)],
 ) -> String {
     let mut output = String::new();
-    for (y, row) in grid.grid.iter().enumerate() {
-        for (x, pipe) in row.iter().enumerate() {
-            if pipe_loop.contains(&(x, y)) {
-                output.push(pipe_to_char(pipe));
-            } else if enclosed.contains(&(x, y)) {
-                output.push('█');
-            } else {
-                output.push('░');
-            }
+    for ((x, y), pipe) in grid.grid.enumerate() {
+        if pipe_loop.contains(&(x, y)) {
+            output.push(pipe_to_char(pipe));
+        } else if enclosed.contains(&(x, y)) {
+            output.push('█');
+        } else {
+            output.push('░');
+        }
+        if x == grid.grid.width() - 1 {
+            output.push('\n');
         }
-        output.push('\n');
     }
     output
 }
 
+/// Draws every tile as 3x3 pixels, the loop with its pipes and the enclosed tiles filled in.
+fn render(grid: &PipeGrid, pipe_loop: &[(usize, usize)], enclosed: &[(usize, usize)]) -> Image {
+    const SCALE: usize = 3;
+    let (width, height) = (grid.grid.width(), grid.grid.height());
+    let on_loop: HashSet<_> = pipe_loop.iter().copied().collect();
+    let mut image = Image::new(width * SCALE, height * SCALE, Rgb::BLACK);
+
+    for &(x, y) in enclosed {
+        for pixel in (0..SCALE).cartesian_product(0..SCALE) {
+            image.set((x * SCALE + pixel.0, y * SCALE + pixel.1), Rgb::YELLOW);
+        }
+    }
+    for &pos in pipe_loop {
+        let center = (pos.0 * SCALE + 1, pos.1 * SCALE + 1);
+        image.set(center, Rgb::WHITE);
+        for offset in grid.get_pipe(&pos).offsets() {
+            let connected = grid.grid.offset(pos, offset).is_some_and(|next| {
+                on_loop.contains(&next) && grid.pipe_neighbors(&next).contains(&pos)
+            });
+            if connected {
+                let arm = (
+                    center.0.checked_add_signed(offset.0).unwrap(),
+                    center.1.checked_add_signed(offset.1).unwrap(),
+                );
+                image.set(arm, Rgb::WHITE);
+            }
+        }
+    }
+    image
+}
+
 impl FromStr for PipeGrid {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid = s
-            .lines()
-            .map(|line| line.chars().map(|c| c.try_into().unwrap()).collect_vec())
-            .collect_vec();
-
+        let grid = Grid::parse(s, |c| c.try_into().ok()).map_err(|err| match err {
+            GridParseError::InvalidCell { x, y, .. } => {
+                ParseError::invalid(parse::char_at(s, (x, y)), "invalid pipe")
+            }
+            err => ParseError::invalid(s, err.to_string()),
+        })?;
         let animal = grid
-            .iter()
-            .enumerate()
-            .find_map(|(y, row)| {
-                row.iter().enumerate().find_map(|(x, pipe)| {
-                    if let Pipe::Animal = pipe {
-                        Some((x, y))
-                    } else {
-                        None
-                    }
-                })
-            })
-            .unwrap();
+            .position(|pipe| matches!(pipe, Pipe::Animal))
+            .ok_or_else(|| ParseError::invalid(s, "the grid has no animal"))?;
 
         Ok(Self { grid, animal })
     }
@@ -283,13 +278,13 @@ impl TryFrom<char> for Pipe {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let grid: PipeGrid = input.parse().unwrap();
+    let grid: PipeGrid = parse::from_str(input).unwrap();
     let pipe_loop = grid.traverse_loop(grid.animal);
     Some(pipe_loop.len() as u32 / 2)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let grid: PipeGrid = input.parse().unwrap();
+    let grid: PipeGrid = parse::from_str(input).unwrap();
     let pipe_loop = grid.traverse_loop(grid.animal);
     let enclosed_tiles = grid.count_enclosed_tiles(pipe_loop);
     Some(enclosed_tiles)
@@ -301,31 +296,31 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+        let result = part_one(&advent_of_code::template::read_file_variant(
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result, Some(4));
 
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+        let result = part_one(&advent_of_code::template::read_file_variant(
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
+        let result = part_two(&advent_of_code::template::read_file_variant(
+            "examples", PUZZLE, 3,
         ));
         assert_eq!(result, Some(4));
 
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 4,
+        let result = part_two(&advent_of_code::template::read_file_variant(
+            "examples", PUZZLE, 4,
         ));
         assert_eq!(result, Some(8));
 
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 5,
+        let result = part_two(&advent_of_code::template::read_file_variant(
+            "examples", PUZZLE, 5,
         ));
         assert_eq!(result, Some(10));
     }
@@ -1,41 +1,24 @@
-advent_of_code::solution!(11);
+use advent_of_code::grid::Grid;
 
-fn parse_grid(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
-}
-
-fn rows_cols_to_expand(grid: &Vec<Vec<char>>) -> (Vec<usize>, Vec<usize>) {
-    let width = grid[0].len();
-    let height = grid.len();
+advent_of_code::solution!(2023, 11);
 
-    let mut rows_to_expand = Vec::new();
-    let mut cols_to_expand = Vec::new();
-
-    for y in 0..height {
-        if grid[y].iter().all(|c| *c == '.') {
-            rows_to_expand.push(y);
-        }
-    }
+fn parse_grid(input: &str) -> Grid<char> {
+    input.parse().unwrap()
+}
 
-    for x in 0..width {
-        if grid.iter().all(|row| row[x] == '.') {
-            cols_to_expand.push(x);
-        }
-    }
+fn rows_cols_to_expand(grid: &Grid<char>) -> (Vec<usize>, Vec<usize>) {
+    let rows_to_expand = (0..grid.height())
+        .filter(|&y| grid.row(y).iter().all(|c| *c == '.'))
+        .collect();
+    let cols_to_expand = (0..grid.width())
+        .filter(|&x| grid.col(x).all(|c| *c == '.'))
+        .collect();
 
     (rows_to_expand, cols_to_expand)
 }
 
-fn parse_galaxies(grid: &Vec<Vec<char>>) -> Vec<Galaxy> {
-    grid.iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(_, c)| **c == '#')
-                .map(move |(x, _)| Galaxy { x, y })
-        })
-        .collect()
+fn parse_galaxies(grid: &Grid<char>) -> Vec<Galaxy> {
+    grid.find_all(&'#').map(|(x, y)| Galaxy { x, y }).collect()
 }
 
 struct Galaxy {
@@ -43,7 +26,7 @@ struct Galaxy {
     y: usize,
 }
 
-fn expand_with_factor(grid: &Vec<Vec<char>>, factor: u64) -> u64 {
+fn expand_with_factor(grid: &Grid<char>, factor: u64) -> u64 {
     let (rows_to_expand, cols_to_expand) = rows_cols_to_expand(&grid);
     let galaxies = parse_galaxies(&grid);
 
@@ -98,13 +81,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(374));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         // assert_eq!(result, Some(1030));
         assert_eq!(result, Some(8410));
     }
@@ -0,0 +1,103 @@
+use advent_of_code::memo::Memo;
+use advent_of_code::prelude::*;
+
+advent_of_code::solution!(2023, 12);
+
+/// Results are cached by the lengths of the remaining springs and runs,
+/// which identify the suffixes of a single record.
+type Cache = Memo<(usize, usize), u64>;
+
+fn count_valid_solutions(line: &[char], runs: &[usize], memo: &mut Cache) -> u64 {
+    if line.is_empty() {
+        return u64::from(runs.is_empty());
+    }
+    if runs.is_empty() {
+        return u64::from(!line.contains(&'#'));
+    }
+
+    let min_remaining_len = runs.iter().sum::<usize>() + runs.len() - 1;
+    if line.len() < min_remaining_len {
+        return 0;
+    }
+
+    let key = (line.len(), runs.len());
+    if let Some(count) = memo.get(&key) {
+        return count;
+    }
+
+    let count = match line[0] {
+        '.' => count_valid_solutions(&line[1..], runs, memo),
+        '#' => count_after_run(line, runs, memo),
+        // otherwise dunno first spot, try both
+        _ => count_valid_solutions(&line[1..], runs, memo) + count_after_run(line, runs, memo),
+    };
+    memo.insert(key, count)
+}
+
+/// Counts the solutions where the first run starts at the first spot.
+fn count_after_run(line: &[char], runs: &[usize], memo: &mut Cache) -> u64 {
+    let (&run, leftover_runs) = runs.split_first().unwrap();
+    if line[..run].contains(&'.') {
+        return 0;
+    }
+    match line.get(run) {
+        None => u64::from(leftover_runs.is_empty()),
+        Some('#') => 0,
+        Some(_) => count_valid_solutions(&line[run + 1..], leftover_runs, memo),
+    }
+}
+
+fn parse_line(line: &str, copies: usize) -> (Vec<char>, Vec<usize>) {
+    let (line, groups) = line.split_once(' ').unwrap();
+    let line = vec![line; copies].join("?").chars().collect_vec();
+    let groups = groups
+        .split(',')
+        .map(|s| s.parse::<usize>().unwrap())
+        .collect_vec()
+        .repeat(copies);
+    (line, groups)
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let result = input
+        .par_lines()
+        .map(|line| parse_line(line, 1))
+        .map(|(line, groups)| count_valid_solutions(&line, &groups, &mut Memo::new()))
+        .sum();
+    Some(result)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let result = input
+        .par_lines()
+        .map(|line| parse_line(line, 5))
+        .map(|(line, groups)| count_valid_solutions(&line, &groups, &mut Memo::new()))
+        .sum();
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(21));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(525152));
+    }
+
+    #[test]
+    fn test_solution_two() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_two(&input);
+        assert_eq!(result, Some(17391848518844));
+    }
+}
@@ -1,11 +1,11 @@
-use itertools::Itertools;
+use advent_of_code::grid::Grid;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(2023, 13);
 
-fn check_horiz_off(input: &Vec<Vec<char>>, reflect_before: usize, target_diff_count: u32) -> u32 {
-    let min_height = (input.len() - reflect_before).min(reflect_before);
-    let mut top = input[..reflect_before].iter().rev().take(min_height);
-    let mut bottom = input[reflect_before..].iter().take(min_height);
+fn check_horiz_off(input: &Grid<char>, reflect_before: usize, target_diff_count: u32) -> u32 {
+    let min_height = (input.height() - reflect_before).min(reflect_before);
+    let mut top = input.rows().take(reflect_before).rev().take(min_height);
+    let mut bottom = input.rows().skip(reflect_before).take(min_height);
 
     let mut diff_count = 0;
     while diff_count <= target_diff_count {
@@ -28,19 +28,14 @@ fn check_horiz_off(input: &Vec<Vec<char>>, reflect_before: usize, target_diff_co
 
 fn reflection_score(input: &str, target_diff_count: u32) -> u32 {
     let orig = input;
-    let input = input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec();
-    for y in 1..input.len() {
+    let input: Grid<char> = input.parse().unwrap();
+    for y in 1..input.height() {
         if check_horiz_off(&input, y, target_diff_count) == target_diff_count {
             return y as u32 * 100;
         }
     }
-    let transposed: Vec<Vec<_>> = (0..input[0].len())
-        .map(|col| (0..input.len()).map(|row| input[row][col]).collect())
-        .collect();
-    for x in 1..transposed.len() {
+    let transposed = input.transpose();
+    for x in 1..transposed.height() {
         if check_horiz_off(&transposed, x, target_diff_count) == target_diff_count {
             return x as u32;
         }
@@ -64,13 +59,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(405));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(400));
     }
 }
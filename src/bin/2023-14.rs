@@ -0,0 +1,167 @@
+use advent_of_code::bitgrid::BitGrid;
+use advent_of_code::cycle;
+use advent_of_code::geometry::Dir4;
+use advent_of_code::grid::Grid;
+use advent_of_code::template::viz_dir;
+use advent_of_code::viz::{Image, Recorder, Rgb};
+
+advent_of_code::solution!(2023, 14);
+
+/// Returns a row with the bits `from..to` set.
+fn span(from: usize, to: usize) -> u128 {
+    let below = |n: usize| 1u128.checked_shl(n as u32).map_or(u128::MAX, |bit| bit - 1);
+    below(to) & !below(from)
+}
+
+struct Platform {
+    cube_rocks: BitGrid,
+}
+
+impl Platform {
+    fn parse(input: &str) -> (Self, BitGrid) {
+        let grid: Grid<char> = input.parse().unwrap();
+        let cube_rocks = BitGrid::from_grid(&grid, |&c| c == '#');
+        let round_rocks = BitGrid::from_grid(&grid, |&c| c == 'O');
+        (Self { cube_rocks }, round_rocks)
+    }
+
+    /// Rolls every round rock as far as it goes, a whole row at a time.
+    fn tilt(&self, rocks: &mut BitGrid, dir: Dir4) {
+        let walls = self.cube_rocks.rows();
+        let height = walls.len();
+        let width = rocks.width();
+        let rows = rocks.rows_mut();
+
+        match dir {
+            Dir4::North | Dir4::South => {
+                let order: Vec<usize> = if dir == Dir4::North {
+                    (0..height).collect()
+                } else {
+                    (0..height).rev().collect()
+                };
+                let next = |y: usize| match dir {
+                    Dir4::North => y.checked_sub(1),
+                    _ => Some(y + 1).filter(|&y| y < height),
+                };
+                // the rows towards the edge have settled already, so rocks fall until blocked.
+                for y in order {
+                    let mut falling = std::mem::take(&mut rows[y]);
+                    let mut at = y;
+                    while let Some(to) = next(at).filter(|_| falling != 0) {
+                        let blocked = rows[to] | walls[to];
+                        rows[at] |= falling & blocked;
+                        falling &= !blocked;
+                        at = to;
+                    }
+                    rows[at] |= falling;
+                }
+            }
+            Dir4::East | Dir4::West => {
+                for (row, &wall) in rows.iter_mut().zip(walls) {
+                    let mut rolled = 0;
+                    let mut start = 0;
+                    let ends = (0..width).filter(|&x| wall >> x & 1 == 1).chain([width]);
+                    for end in ends {
+                        let count = (*row & span(start, end)).count_ones() as usize;
+                        rolled |= if dir == Dir4::West {
+                            span(start, start + count)
+                        } else {
+                            span(end - count, end)
+                        };
+                        start = end + 1;
+                    }
+                    *row = rolled;
+                }
+            }
+        }
+    }
+
+    fn spin_cycle(&self, rocks: &BitGrid) -> BitGrid {
+        let mut rocks = rocks.clone();
+        for dir in [Dir4::North, Dir4::West, Dir4::South, Dir4::East] {
+            self.tilt(&mut rocks, dir);
+        }
+        rocks
+    }
+
+    /// Animates the platform through the given states of the round rocks.
+    fn record(&self, states: &[BitGrid]) -> Recorder {
+        let mut recorder = Recorder::new(20);
+        for rocks in states {
+            let mut frame = Image::from_bits(rocks, Rgb::WHITE, Rgb::BLACK);
+            for pos in self.cube_rocks.iter_ones() {
+                frame.set(pos, Rgb::GREY);
+            }
+            recorder.push(frame.scaled(4)).unwrap();
+        }
+        recorder
+    }
+}
+
+fn total_load(rocks: &BitGrid) -> u32 {
+    let height = rocks.height();
+    let load = rocks
+        .rows()
+        .iter()
+        .enumerate()
+        .map(|(y, row)| (height - y) * row.count_ones() as usize)
+        .sum::<usize>();
+    load as u32
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let (platform, mut rocks) = Platform::parse(input);
+    platform.tilt(&mut rocks, Dir4::North);
+
+    Some(total_load(&rocks))
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    const TARGET_CYCLE: usize = 1_000_000_000;
+
+    let (platform, rocks) = Platform::parse(input);
+    let history = cycle::detect(rocks, |rocks| platform.spin_cycle(rocks));
+    if let Some(dir) = viz_dir() {
+        let path = dir.join(format!("{PUZZLE}-spin-cycles.gif"));
+        platform.record(history.states()).save(path).unwrap();
+    }
+
+    Some(total_load(history.nth(TARGET_CYCLE)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(136));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(64));
+    }
+
+    #[test]
+    fn test_spin_cycle() {
+        let (platform, rocks) =
+            Platform::parse(&advent_of_code::template::read_file("examples", PUZZLE));
+        let expected = "\
+.....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O...#.
+......OOOO
+#...O###..
+#..OO#....";
+        let after_one = platform.spin_cycle(&rocks);
+        let grid: Grid<char> = expected.parse().unwrap();
+        assert_eq!(after_one, BitGrid::from_grid(&grid, |&c| c == 'O'));
+    }
+}
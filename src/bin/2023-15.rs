@@ -1,6 +1,6 @@
 use indexmap::IndexMap;
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(2023, 15);
 
 fn hash_value(step: &str) -> u32 {
     step.chars().fold(0, |acc, c| ((acc + c as u32) * 17) % 256)
@@ -45,13 +45,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1320));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(145));
     }
 }
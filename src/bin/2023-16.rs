@@ -0,0 +1,115 @@
+use advent_of_code::bitgrid::BitGrid;
+use advent_of_code::geometry::Dir4;
+use advent_of_code::grid::Grid;
+use advent_of_code::prelude::*;
+use advent_of_code::template::viz_dir;
+use advent_of_code::viz::{Image, Recorder, Rgb};
+
+advent_of_code::solution!(2023, 16);
+
+/// Follows the beam and returns the energized tiles. Beams are remembered by direction,
+/// in one bit grid each, so every tile is entered at most once per direction.
+/// All beams advance one tile per round, after which `on_round` sees the tiles visited so far.
+fn traverse_beam(
+    grid: &Grid<char>,
+    start_pos: (usize, usize),
+    dir: Dir4,
+    mut on_round: impl FnMut(&[BitGrid; 4]),
+) -> BitGrid {
+    let mut visited: [BitGrid; 4] =
+        std::array::from_fn(|_| BitGrid::new(grid.width(), grid.height()));
+    let mut beam_heads = vec![(start_pos, dir)];
+    let mut next_heads = Vec::new();
+
+    while !beam_heads.is_empty() {
+        for (pos, dir) in beam_heads.drain(..) {
+            if visited[dir as usize].set(pos, true) {
+                continue;
+            }
+
+            let next_dirs = match grid[pos] {
+                '/' => [Some(dir.reflect_slash()), None],
+                '\\' => [Some(dir.reflect_backslash()), None],
+                '-' if dir.is_vertical() => [Some(Dir4::West), Some(Dir4::East)],
+                '|' if dir.is_horizontal() => [Some(Dir4::North), Some(Dir4::South)],
+                _ => [Some(dir), None],
+            };
+            next_heads.extend(next_dirs.into_iter().flatten().filter_map(|dir| {
+                let next = grid.offset(pos, dir.offset::<isize>().into())?;
+                Some((next, dir))
+            }));
+        }
+        std::mem::swap(&mut beam_heads, &mut next_heads);
+        on_round(&visited);
+    }
+
+    energized(&visited)
+}
+
+fn energized([north, east, south, west]: &[BitGrid; 4]) -> BitGrid {
+    &(north | east) | &(south | west)
+}
+
+/// Draws the mirrors and splitters, with the tiles the beam passed lit up.
+fn render(grid: &Grid<char>, energized: &BitGrid) -> Image {
+    let mut frame = Image::from_grid(grid, |&c| if c == '.' { Rgb::BLACK } else { Rgb::GREY });
+    for pos in energized.iter_ones() {
+        let colour = if grid[pos] == '.' {
+            Rgb::YELLOW
+        } else {
+            Rgb::WHITE
+        };
+        frame.set(pos, colour);
+    }
+    frame.scaled(4)
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().unwrap();
+
+    let Some(dir) = viz_dir() else {
+        let energized = traverse_beam(&grid, (0, 0), Dir4::East, |_| {});
+        return Some(energized.count_ones() as u32);
+    };
+
+    let mut recorder = Recorder::new(4);
+    let energized = traverse_beam(&grid, (0, 0), Dir4::East, |visited| {
+        recorder.push(render(&grid, &energized(visited))).unwrap();
+    });
+    let path = dir.join(format!("{PUZZLE}-beam.gif"));
+    recorder.save(path).unwrap();
+    Some(energized.count_ones() as u32)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().unwrap();
+    let (width, height) = (grid.width(), grid.height());
+
+    let mut starting_points = Vec::new();
+    starting_points.extend((0..height).map(|y| ((0, y), Dir4::East)));
+    starting_points.extend((0..height).map(|y| ((width - 1, y), Dir4::West)));
+    starting_points.extend((0..width).map(|x| ((x, 0), Dir4::South)));
+    starting_points.extend((0..width).map(|x| ((x, height - 1), Dir4::North)));
+
+    starting_points
+        .into_par_iter()
+        .map(|(pos, dir)| traverse_beam(&grid, pos, dir, |_| {}).count_ones() as u32)
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(46));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(51));
+    }
+}
@@ -0,0 +1,60 @@
+use advent_of_code::geometry::{Dir4, Point2, PointIndex};
+use advent_of_code::grid::Grid;
+use advent_of_code::search::dijkstra_buckets;
+
+advent_of_code::solution!(2023, 17);
+
+/// The crucible's position, heading, and how many blocks it has moved in a straight line.
+type State = (Point2<i32>, Dir4, u32);
+
+fn find_path(map: &Grid<u32>, min_straight_distance: u32, max_straight_distance: u32) -> u32 {
+    let starts = [Dir4::East, Dir4::South].map(|dir| (Point2::new(0, 0), dir, 0));
+    let target = Point2::new(map.width() as i32 - 1, map.height() as i32 - 1);
+
+    let successors = |&(position, direction, distance): &State| {
+        let mut directions = vec![];
+        if distance < max_straight_distance {
+            directions.push(direction);
+        }
+        if distance >= min_straight_distance {
+            directions.extend([direction.turn_left(), direction.turn_right()]);
+        }
+
+        directions.into_iter().filter_map(move |next| {
+            let position = position.step(next);
+            let heat = *map.at(position)?;
+            let distance = if next == direction { distance + 1 } else { 1 };
+            Some(((position, next, distance), heat as usize))
+        })
+    };
+
+    let result = dijkstra_buckets(starts, successors, |&(position, _, _)| position == target);
+    result.cost().expect("no path found") as u32
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid = Grid::parse(input, |c| c.to_digit(10)).unwrap();
+    Some(find_path(&grid, 0, 3))
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let grid = Grid::parse(input, |c| c.to_digit(10)).unwrap();
+    Some(find_path(&grid, 4, 10))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(102));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(94));
+    }
+}
@@ -0,0 +1,114 @@
+use advent_of_code::geometry::{Dir4, Point2};
+use advent_of_code::math;
+
+advent_of_code::solution!(2023, 18);
+
+struct Grid {
+    nodes: Vec<Point2<i64>>,
+}
+
+fn parse_direction(dir: &str) -> Dir4 {
+    let dir = dir.chars().next().expect("direction to be given");
+    Dir4::try_from(dir).expect("direction to be valid")
+}
+
+fn parse_direction_from_number(dir: usize) -> Dir4 {
+    [Dir4::East, Dir4::South, Dir4::West, Dir4::North][dir]
+}
+
+impl Grid {
+    fn new_part_1(contents: &str) -> Self {
+        let mut nodes = vec![];
+
+        let lines = contents.lines();
+        let mut cur = Point2::origin();
+
+        for line in lines {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+
+            let dir = parts[0].to_owned();
+            let steps = parts[1].parse::<i64>().expect("steps are a number");
+
+            let dir = parse_direction(&dir);
+
+            cur = cur.step_n(dir, steps);
+            nodes.push(cur);
+        }
+
+        Self { nodes }
+    }
+
+    fn new_part_2(contents: &str) -> Self {
+        let mut nodes = vec![];
+
+        let lines = contents.lines();
+        let mut cur = Point2::origin();
+
+        for line in lines {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+
+            let color = parts[2];
+            let dir = usize::from_str_radix(&color[7..8], 16).expect("direction to be a number");
+            let steps =
+                usize::from_str_radix(&color[2..7], 16).expect("steps to be a number") as i64;
+
+            let dir = parse_direction_from_number(dir);
+
+            cur = cur.step_n(dir, steps);
+            nodes.push(cur);
+        }
+
+        Self { nodes }
+    }
+
+    fn area(&self) -> i64 {
+        math::lattice_points(&self.nodes)
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let grid = Grid::new_part_1(input);
+    let result = grid.area();
+    Some(result as u64)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let grid = Grid::new_part_2(input);
+    let result = grid.area();
+    Some(result as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(62));
+    }
+
+    #[test]
+    fn test_solution_one() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_one(&input);
+        assert_eq!(result, Some(35401));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(952408144115));
+    }
+
+    #[test]
+    fn test_solution_two() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_two(&input);
+        assert_eq!(result, Some(48020869073824));
+    }
+}
@@ -0,0 +1,749 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use advent_of_code::interval::Interval;
+use advent_of_code::parse::{self, ParseError, ParseErrorKind};
+use advent_of_code::prelude::*;
+use advent_of_code::template;
+use indexmap::IndexMap;
+
+advent_of_code::solution!(2023, 19);
+
+const CATEGORIES: [(&str, usize); 4] = [("x", 0), ("m", 1), ("a", 2), ("s", 3)];
+
+#[derive(Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+struct Part {
+    x: u64,
+    m: u64,
+    a: u64,
+    s: u64,
+}
+
+impl Part {
+    fn ratings(&self) -> [u64; 4] {
+        [self.x, self.m, self.a, self.s]
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Part { x, m, a, s } = self;
+        write!(f, "{{x={x},m={m},a={a},s={s}}}")
+    }
+}
+
+/// A comparison of one rating, like `a<2006`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Condition {
+    category: usize,
+    op: Ordering,
+    value: u64,
+}
+
+impl Condition {
+    fn holds(&self, part: &Part) -> bool {
+        part.ratings()[self.category].cmp(&self.value) == self.op
+    }
+
+    /// Splits the parts into the ones meeting the condition and the others.
+    fn split(&self, parts: &PartBox) -> (PartBox, PartBox) {
+        match self.op {
+            Ordering::Less => parts.split_at(self.category, self.value),
+            _ => {
+                let (rest, matching) = parts.split_at(self.category, self.value + 1);
+                (matching, rest)
+            }
+        }
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (name, _) = CATEGORIES[self.category];
+        let op = if self.op == Ordering::Less { '<' } else { '>' };
+        write!(f, "{name}{op}{}", self.value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    condition: Condition,
+    target: String,
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (condition, target) = parse::split_once(s, ":")?;
+        let split = condition.find(['<', '>']).ok_or_else(|| {
+            ParseError::new(
+                &condition[condition.len()..],
+                ParseErrorKind::Expected("< or >".to_string()),
+            )
+        })?;
+        let (category, rest) = condition.split_at(split);
+        let (op, value) = rest.split_at(1);
+
+        let condition = Condition {
+            category: parse::one_of(category, &CATEGORIES)?,
+            op: if op == "<" {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            },
+            value: parse::int(value)?,
+        };
+        Ok(Rule {
+            condition,
+            target: target.to_string(),
+        })
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.condition, self.target)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Workflow {
+    rules: Vec<Rule>,
+    fallback: String,
+}
+
+impl FromStr for Workflow {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',').collect::<Vec<_>>();
+        let fallback = parts.pop().unwrap_or_default().to_string();
+        let rules = parts
+            .into_iter()
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Workflow { rules, fallback })
+    }
+}
+
+/// The parts whose ratings lie in an interval per category, in the order x, m, a, s.
+#[derive(Debug, Clone, Copy)]
+struct PartBox([Interval<u64>; 4]);
+
+impl Default for PartBox {
+    fn default() -> Self {
+        Self([Interval::closed(1, 4000); 4])
+    }
+}
+
+impl PartBox {
+    fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    fn possibilities(&self) -> u64 {
+        self.0.iter().map(Interval::len).product()
+    }
+
+    /// Splits the box into the parts rated below `at` in the category and the remaining ones.
+    fn split_at(&self, category: usize, at: u64) -> (Self, Self) {
+        let (mut below, mut above) = (*self, *self);
+        (below.0[category], above.0[category]) = self.0[category].split_at(at);
+        (below, above)
+    }
+}
+
+/// The workflows compiled into a single tree, starting at `in`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Decision {
+    Accept,
+    Reject,
+    /// The parts move on to the workflow with this index.
+    Workflow {
+        id: usize,
+        next: Box<Decision>,
+    },
+    /// The parts meeting the condition of the workflow's `rule`-th rule take `matching`,
+    /// the others `rest`.
+    Test {
+        rule: usize,
+        condition: Condition,
+        matching: Box<Decision>,
+        rest: Box<Decision>,
+    },
+}
+
+impl Decision {
+    fn accepts(&self, part: &Part) -> bool {
+        match self {
+            Decision::Accept => true,
+            Decision::Reject => false,
+            Decision::Workflow { next, .. } => next.accepts(part),
+            Decision::Test {
+                condition,
+                matching,
+                rest,
+                ..
+            } => {
+                if condition.holds(part) {
+                    matching.accepts(part)
+                } else {
+                    rest.accepts(part)
+                }
+            }
+        }
+    }
+
+    /// Counts the accepted parts among `parts` exactly, by splitting the box at every test.
+    fn count_accepted(&self, parts: PartBox) -> u64 {
+        if parts.is_empty() {
+            return 0;
+        }
+        match self {
+            Decision::Accept => parts.possibilities(),
+            Decision::Reject => 0,
+            Decision::Workflow { next, .. } => next.count_accepted(parts),
+            Decision::Test {
+                condition,
+                matching,
+                rest,
+                ..
+            } => {
+                let (matched, remaining) = condition.split(&parts);
+                matching.count_accepted(matched) + rest.count_accepted(remaining)
+            }
+        }
+    }
+
+    /// Returns the number of tests in the tree.
+    fn tests(&self) -> usize {
+        match self {
+            Decision::Accept | Decision::Reject => 0,
+            Decision::Workflow { next, .. } => next.tests(),
+            Decision::Test { matching, rest, .. } => 1 + matching.tests() + rest.tests(),
+        }
+    }
+
+    /// Returns whether both trees decide the same way with the same tests,
+    /// regardless of the workflows they came from.
+    fn equivalent(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Decision::Workflow { next, .. }, other) | (other, Decision::Workflow { next, .. }) => {
+                next.equivalent(other)
+            }
+            (
+                Decision::Test {
+                    condition: a,
+                    matching: a_matching,
+                    rest: a_rest,
+                    ..
+                },
+                Decision::Test {
+                    condition: b,
+                    matching: b_matching,
+                    rest: b_rest,
+                    ..
+                },
+            ) => a == b && a_matching.equivalent(b_matching) && a_rest.equivalent(b_rest),
+            (a, b) => a == b,
+        }
+    }
+}
+
+/// How the rules of a workflow are used, collected while simplifying the tree.
+#[derive(Debug, Clone, Default)]
+struct Usage {
+    entered: bool,
+    accepts: bool,
+    rules: Vec<RuleUsage>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct RuleUsage {
+    evaluated: bool,
+    matched: bool,
+    unmatched: bool,
+    /// Whether the outcome ever depends on the rule.
+    decisive: bool,
+}
+
+/// Something about the workflows that does not affect which parts are accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Finding {
+    /// No part ever reaches the workflow.
+    UnreachableWorkflow { workflow: String },
+    /// Every part reaching the workflow is rejected.
+    DeadWorkflow { workflow: String },
+    /// No part reaches the rule, as the rules before it take them all.
+    UnreachableRule { workflow: String, rule: String },
+    /// The condition is false for every part reaching the rule.
+    NeverMatches { workflow: String, rule: String },
+    /// The condition is true for every part reaching the rule, so the rules after it are unused.
+    AlwaysMatches { workflow: String, rule: String },
+    /// The parts end up the same way whether or not they meet the condition.
+    Redundant { workflow: String, rule: String },
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Finding::UnreachableWorkflow { workflow } => {
+                write!(f, "{workflow}: no part reaches the workflow")
+            }
+            Finding::DeadWorkflow { workflow } => {
+                write!(f, "{workflow}: every part is rejected")
+            }
+            Finding::UnreachableRule { workflow, rule } => {
+                write!(f, "{workflow}: no part reaches `{rule}`")
+            }
+            Finding::NeverMatches { workflow, rule } => {
+                write!(f, "{workflow}: `{rule}` never matches")
+            }
+            Finding::AlwaysMatches { workflow, rule } => {
+                write!(f, "{workflow}: `{rule}` always matches")
+            }
+            Finding::Redundant { workflow, rule } => {
+                write!(f, "{workflow}: `{rule}` does not change the outcome")
+            }
+        }
+    }
+}
+
+/// The workflows a part passes, and whether it is accepted in the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Route<'a> {
+    workflows: Vec<&'a str>,
+    accepted: bool,
+}
+
+/// Prints like `in -> px -> qkq -> A`.
+impl Display for Route<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verdict = if self.accepted { "A" } else { "R" };
+        write!(f, "{} -> {verdict}", self.workflows.join(" -> "))
+    }
+}
+
+/// An error which can be returned when compiling workflows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CompileError {
+    UnknownWorkflow(String),
+    /// The workflow can send parts back to itself, so they might never be decided.
+    Cycle(String),
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompileError::UnknownWorkflow(name) => write!(f, "there is no workflow `{name}`."),
+            CompileError::Cycle(name) => write!(f, "workflow `{name}` can send parts to itself."),
+        }
+    }
+}
+
+impl Error for CompileError {}
+
+struct RuleEngine {
+    workflows: IndexMap<String, Workflow>,
+    tree: Decision,
+}
+
+impl RuleEngine {
+    fn compile(workflows: IndexMap<String, Workflow>) -> Result<Self, CompileError> {
+        let mut engine = Self {
+            workflows,
+            tree: Decision::Reject,
+        };
+        engine.tree = engine.compile_target("in", &mut vec![])?;
+        Ok(engine)
+    }
+
+    /// Compiles the workflows reachable from `target`. `path` holds the workflows being
+    /// compiled, to detect cycles.
+    fn compile_target(
+        &self,
+        target: &str,
+        path: &mut Vec<usize>,
+    ) -> Result<Decision, CompileError> {
+        match target {
+            "A" => return Ok(Decision::Accept),
+            "R" => return Ok(Decision::Reject),
+            _ => {}
+        }
+        let (id, _, workflow) = self
+            .workflows
+            .get_full(target)
+            .ok_or_else(|| CompileError::UnknownWorkflow(target.to_string()))?;
+        if path.contains(&id) {
+            return Err(CompileError::Cycle(target.to_string()));
+        }
+
+        path.push(id);
+        let mut next = self.compile_target(&workflow.fallback, path)?;
+        for (rule, Rule { condition, target }) in workflow.rules.iter().enumerate().rev() {
+            next = Decision::Test {
+                rule,
+                condition: *condition,
+                matching: Box::new(self.compile_target(target, path)?),
+                rest: Box::new(next),
+            };
+        }
+        path.pop();
+
+        Ok(Decision::Workflow {
+            id,
+            next: Box::new(next),
+        })
+    }
+
+    /// Returns the route of the part through the workflows.
+    fn route(&self, part: &Part) -> Route<'_> {
+        let mut workflows = vec![];
+        let mut node = &self.tree;
+        loop {
+            node = match node {
+                Decision::Accept | Decision::Reject => {
+                    return Route {
+                        workflows,
+                        accepted: *node == Decision::Accept,
+                    }
+                }
+                Decision::Workflow { id, next } => {
+                    workflows.push(self.workflows.get_index(*id).unwrap().0.as_str());
+                    next
+                }
+                Decision::Test {
+                    condition,
+                    matching,
+                    rest,
+                    ..
+                } => {
+                    if condition.holds(part) {
+                        matching
+                    } else {
+                        rest
+                    }
+                }
+            };
+        }
+    }
+
+    fn count_accepted(&self) -> u64 {
+        self.tree.count_accepted(PartBox::default())
+    }
+
+    /// Returns an equivalent tree without tests that cannot fail, cannot succeed, or do not
+    /// change the outcome. It no longer knows about workflows.
+    fn simplified(&self) -> Decision {
+        self.simplify(
+            &self.tree,
+            PartBox::default(),
+            &mut vec![],
+            &mut self.usage(),
+        )
+    }
+
+    /// Finds the workflows and rules which do not affect the outcome.
+    fn analyze(&self) -> Vec<Finding> {
+        let mut usage = self.usage();
+        self.simplify(&self.tree, PartBox::default(), &mut vec![], &mut usage);
+
+        let mut findings = vec![];
+        for ((name, workflow), usage) in self.workflows.iter().zip(usage) {
+            let workflow_name = name.clone();
+            if !usage.entered {
+                findings.push(Finding::UnreachableWorkflow {
+                    workflow: workflow_name,
+                });
+                continue;
+            }
+            if !usage.accepts {
+                findings.push(Finding::DeadWorkflow {
+                    workflow: workflow_name,
+                });
+                continue;
+            }
+            for (rule, used) in workflow.rules.iter().zip(usage.rules) {
+                let (workflow, rule) = (workflow_name.clone(), rule.to_string());
+                let finding = if !used.evaluated {
+                    Finding::UnreachableRule { workflow, rule }
+                } else if !used.matched {
+                    Finding::NeverMatches { workflow, rule }
+                } else if !used.unmatched {
+                    Finding::AlwaysMatches { workflow, rule }
+                } else if !used.decisive {
+                    Finding::Redundant { workflow, rule }
+                } else {
+                    continue;
+                };
+                findings.push(finding);
+            }
+        }
+        findings
+    }
+
+    fn usage(&self) -> Vec<Usage> {
+        self.workflows
+            .values()
+            .map(|workflow| Usage {
+                rules: vec![RuleUsage::default(); workflow.rules.len()],
+                ..Usage::default()
+            })
+            .collect()
+    }
+
+    /// Simplifies the tree for the parts reaching it, recording how the rules are used.
+    /// `path` holds the workflows the parts passed.
+    fn simplify(
+        &self,
+        node: &Decision,
+        parts: PartBox,
+        path: &mut Vec<usize>,
+        usage: &mut [Usage],
+    ) -> Decision {
+        match node {
+            Decision::Accept => {
+                for &id in path.iter() {
+                    usage[id].accepts = true;
+                }
+                Decision::Accept
+            }
+            Decision::Reject => Decision::Reject,
+            Decision::Workflow { id, next } => {
+                usage[*id].entered = true;
+                path.push(*id);
+                let next = self.simplify(next, parts, path, usage);
+                path.pop();
+                next
+            }
+            Decision::Test {
+                rule,
+                condition,
+                matching,
+                rest,
+            } => {
+                let (matched, remaining) = condition.split(&parts);
+                let workflow = *path.last().expect("tests belong to a workflow");
+                let used = &mut usage[workflow].rules[*rule];
+                used.evaluated = true;
+                used.matched |= !matched.is_empty();
+                used.unmatched |= !remaining.is_empty();
+
+                let mut branch = |node, parts: PartBox| {
+                    (!parts.is_empty()).then(|| self.simplify(node, parts, path, usage))
+                };
+                let matching = branch(matching, matched);
+                let rest = branch(rest, remaining);
+                match (matching, rest) {
+                    (Some(matching), Some(rest)) if !matching.equivalent(&rest) => {
+                        usage[workflow].rules[*rule].decisive = true;
+                        Decision::Test {
+                            rule: *rule,
+                            condition: *condition,
+                            matching: Box::new(matching),
+                            rest: Box::new(rest),
+                        }
+                    }
+                    (Some(decision), _) | (None, Some(decision)) => decision,
+                    (None, None) => unreachable!("the parts reaching a test are never empty"),
+                }
+            }
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<(IndexMap<String, Workflow>, Vec<Part>), ParseError> {
+    let (workflows, parts) = parse::split_once(input, "\n\n")?;
+    let workflows = parse::lines(workflows, |line| {
+        let (name, workflow) = parse::split_once(line, "{")?;
+        let workflow = workflow.strip_suffix('}').ok_or_else(|| {
+            ParseError::new(
+                &line[line.len()..],
+                ParseErrorKind::Expected("}".to_string()),
+            )
+        })?;
+        Ok((name.to_string(), workflow.parse()?))
+    })?
+    .into_iter()
+    .collect();
+
+    let parts = parse::lines(parts, |line| {
+        let [x, m, a, s] = parse::ints_n(line)?;
+        Ok(Part { x, m, a, s })
+    })
+    .map_err(|err| err.locate(input))?;
+
+    Ok((workflows, parts))
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let (workflows, parts) = parse_input(input).unwrap();
+    let engine = RuleEngine::compile(workflows).unwrap();
+
+    if template::is_tracing() {
+        for part in &parts {
+            println!("{part}: {}", engine.route(part));
+        }
+    }
+
+    let result = parts
+        .par_iter()
+        .filter(|part| engine.tree.accepts(part))
+        .map(|part| part.ratings().iter().sum::<u64>())
+        .sum::<u64>();
+
+    Some(result)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let (workflows, _) = parse_input(input).unwrap();
+    let engine = RuleEngine::compile(workflows).unwrap();
+
+    if template::is_tracing() {
+        for finding in engine.analyze() {
+            println!("{finding}");
+        }
+        let tests = engine.tree.tests();
+        println!(
+            "simplified to {} of {tests} tests",
+            engine.simplified().tests()
+        );
+    }
+
+    Some(engine.count_accepted())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(input: &str) -> (RuleEngine, Vec<Part>) {
+        let (workflows, parts) = parse_input(input).unwrap();
+        (RuleEngine::compile(workflows).unwrap(), parts)
+    }
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(19114));
+    }
+
+    #[test]
+    fn test_solution_one() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_one(&input);
+        assert_eq!(result, Some(421983));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(167409079868000));
+    }
+
+    #[test]
+    fn test_solution_two() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_two(&input);
+        assert_eq!(result, Some(129249871135292));
+    }
+
+    #[test]
+    fn test_routes() {
+        let (engine, parts) = load(&advent_of_code::template::read_file("examples", PUZZLE));
+        let routes = parts
+            .iter()
+            .map(|part| engine.route(part).to_string())
+            .collect_vec();
+        assert_eq!(
+            routes,
+            [
+                "in -> qqz -> qs -> lnx -> A",
+                "in -> px -> rfg -> gd -> R",
+                "in -> qqz -> hdj -> pv -> A",
+                "in -> px -> qkq -> crn -> R",
+                "in -> px -> rfg -> A",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simplify() {
+        let (engine, parts) = load(&advent_of_code::template::read_file("examples", PUZZLE));
+        let simplified = engine.simplified();
+        assert!(simplified.tests() < engine.tree.tests());
+        assert_eq!(
+            simplified.count_accepted(PartBox::default()),
+            engine.count_accepted()
+        );
+        for part in &parts {
+            assert_eq!(simplified.accepts(part), engine.tree.accepts(part));
+        }
+    }
+
+    #[test]
+    fn test_analyze() {
+        let (engine, _) = load(&advent_of_code::template::read_file("examples", PUZZLE));
+        let findings = engine
+            .analyze()
+            .iter()
+            .map(ToString::to_string)
+            .collect_vec();
+        assert_eq!(
+            findings,
+            [
+                "lnx: `m>1548:A` does not change the outcome",
+                "qs: `s>3448:A` does not change the outcome",
+                "gd: every part is rejected",
+            ]
+        );
+
+        let (engine, _) = load(
+            "\
+in{x<10:a,x<5:R,x>4000:R,b}
+a{A}
+b{m>0:A,s<5:R,R}
+c{A}
+
+",
+        );
+        let findings = engine
+            .analyze()
+            .iter()
+            .map(ToString::to_string)
+            .collect_vec();
+        assert_eq!(
+            findings,
+            [
+                "in: `x<10:a` does not change the outcome",
+                "in: `x<5:R` never matches",
+                "in: `x>4000:R` never matches",
+                "b: `m>0:A` always matches",
+                "b: no part reaches `s<5:R`",
+                "c: no part reaches the workflow",
+            ]
+        );
+        assert_eq!(engine.simplified(), Decision::Accept);
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let compile = |input: &str| RuleEngine::compile(parse_input(input).unwrap().0).err();
+        assert_eq!(
+            compile("in{x<10:a,R}\n\n"),
+            Some(CompileError::UnknownWorkflow("a".to_string()))
+        );
+        assert_eq!(
+            compile("in{x<10:a,R}\na{in}\n\n"),
+            Some(CompileError::Cycle("in".to_string()))
+        );
+    }
+}
@@ -0,0 +1,511 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::prelude::*;
+use advent_of_code::{math, template};
+use indexmap::IndexSet;
+
+advent_of_code::solution!(2023, 20);
+
+/// A module, identified by its index in the network.
+type ModuleId = usize;
+
+/// The button is not listed in the input, but sends the first pulse of every press.
+const BUTTON: ModuleId = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pulse {
+    Low,
+    High,
+}
+
+impl Display for Pulse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pulse::Low => write!(f, "low"),
+            Pulse::High => write!(f, "high"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModuleKind {
+    Button,
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    /// Modules without a type, like `rx`, which only receive pulses.
+    Output,
+}
+
+/// A pulse sent from one module to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Signal {
+    from: ModuleId,
+    to: ModuleId,
+    pulse: Pulse,
+}
+
+/// The state of all modules between button presses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct State {
+    /// Bit `id` is set for the flip-flops that are on.
+    flip_flops: u128,
+    /// Bit `i` of a conjunction's word is set if its `i`-th input last sent a high pulse.
+    memory: Vec<u64>,
+}
+
+#[derive(Debug, Clone)]
+struct Network {
+    names: IndexSet<String>,
+    kinds: Vec<ModuleKind>,
+    targets: Vec<Vec<ModuleId>>,
+    inputs: Vec<Vec<ModuleId>>,
+    /// For every target of a module, the index of the module among the target's inputs.
+    slots: Vec<Vec<usize>>,
+}
+
+impl Network {
+    /// # Panics
+    /// Panics if there are more than 128 modules, or a conjunction has more than 64 inputs.
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut network = Self {
+            names: IndexSet::new(),
+            kinds: vec![],
+            targets: vec![],
+            inputs: vec![],
+            slots: vec![],
+        };
+        network.add("button", ModuleKind::Button);
+        network.add("broadcaster", ModuleKind::Broadcaster);
+        network.targets[BUTTON].push(1);
+
+        for (module, targets) in parse::lines(input, parse::arrow_list)? {
+            let (name, kind) = match module.split_at(module.len().min(1)) {
+                ("%", name) => (name, ModuleKind::FlipFlop),
+                ("&", name) => (name, ModuleKind::Conjunction),
+                _ if module == "broadcaster" => (module, ModuleKind::Broadcaster),
+                _ => (module, ModuleKind::Output),
+            };
+            let id = network.add(name, kind);
+            network.targets[id] = targets
+                .into_iter()
+                .map(|target| network.add(target, ModuleKind::Output))
+                .collect();
+        }
+
+        for from in 0..network.len() {
+            let slots = network.targets[from]
+                .iter()
+                .map(|&to| {
+                    network.inputs[to].push(from);
+                    network.inputs[to].len() - 1
+                })
+                .collect();
+            network.slots[from] = slots;
+        }
+
+        assert!(network.len() <= 128, "at most 128 modules are supported");
+        assert!(
+            network.inputs.iter().all(|inputs| inputs.len() <= 64),
+            "conjunctions may have at most 64 inputs"
+        );
+        Ok(network)
+    }
+
+    /// Returns the module with the name, adding it if it is new. Modules that were only
+    /// mentioned as targets so far take the kind of their definition.
+    fn add(&mut self, name: &str, kind: ModuleKind) -> ModuleId {
+        let (id, is_new) = self.names.insert_full(name.to_string());
+        if is_new {
+            self.kinds.push(kind);
+            self.targets.push(vec![]);
+            self.inputs.push(vec![]);
+            self.slots.push(vec![]);
+        } else if kind != ModuleKind::Output {
+            self.kinds[id] = kind;
+        }
+        id
+    }
+
+    fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    fn id(&self, name: &str) -> Option<ModuleId> {
+        self.names.get_index_of(name)
+    }
+
+    fn name(&self, id: ModuleId) -> &str {
+        &self.names[id]
+    }
+
+    /// Returns the state before the first press, with every flip-flop off
+    /// and every conjunction remembering low pulses.
+    fn initial_state(&self) -> State {
+        State {
+            flip_flops: 0,
+            memory: vec![0; self.len()],
+        }
+    }
+
+    /// Pushes the button once, showing every pulse to the observer in the order it is handled.
+    fn press(&self, state: &mut State, press: usize, observer: &mut impl Observer) {
+        let mut queue = VecDeque::from([(
+            Signal {
+                from: BUTTON,
+                to: self.targets[BUTTON][0],
+                pulse: Pulse::Low,
+            },
+            0,
+        )]);
+
+        while let Some((signal, slot)) = queue.pop_front() {
+            observer.observe(press, &signal);
+
+            let to = signal.to;
+            let pulse = match self.kinds[to] {
+                ModuleKind::Broadcaster => signal.pulse,
+                ModuleKind::FlipFlop if signal.pulse == Pulse::Low => {
+                    state.flip_flops ^= 1 << to;
+                    if state.flip_flops >> to & 1 == 1 {
+                        Pulse::High
+                    } else {
+                        Pulse::Low
+                    }
+                }
+                ModuleKind::Conjunction => {
+                    let memory = &mut state.memory[to];
+                    match signal.pulse {
+                        Pulse::High => *memory |= 1 << slot,
+                        Pulse::Low => *memory &= !(1 << slot),
+                    }
+                    if memory.count_ones() as usize == self.inputs[to].len() {
+                        Pulse::Low
+                    } else {
+                        Pulse::High
+                    }
+                }
+                _ => continue,
+            };
+
+            queue.extend(
+                self.targets[to]
+                    .iter()
+                    .zip(&self.slots[to])
+                    .map(|(&target, &slot)| {
+                        let signal = Signal {
+                            from: to,
+                            to: target,
+                            pulse,
+                        };
+                        (signal, slot)
+                    }),
+            );
+        }
+    }
+
+    /// Describes a pulse like the puzzle does, e.g. `broadcaster -low-> a`.
+    fn describe(&self, signal: &Signal) -> String {
+        format!(
+            "{} -{}-> {}",
+            self.name(signal.from),
+            signal.pulse,
+            self.name(signal.to)
+        )
+    }
+}
+
+/// Watches the pulses of a network, in the order they are handled.
+trait Observer {
+    fn observe(&mut self, press: usize, signal: &Signal);
+}
+
+impl<T: Observer> Observer for &mut T {
+    fn observe(&mut self, press: usize, signal: &Signal) {
+        (**self).observe(press, signal);
+    }
+}
+
+impl<A: Observer, B: Observer> Observer for (A, B) {
+    fn observe(&mut self, press: usize, signal: &Signal) {
+        self.0.observe(press, signal);
+        self.1.observe(press, signal);
+    }
+}
+
+impl<T: Observer> Observer for Option<T> {
+    fn observe(&mut self, press: usize, signal: &Signal) {
+        if let Some(observer) = self {
+            observer.observe(press, signal);
+        }
+    }
+}
+
+/// Counts the pulses sent.
+#[derive(Debug, Default)]
+struct PulseCounter {
+    low: u64,
+    high: u64,
+}
+
+impl Observer for PulseCounter {
+    fn observe(&mut self, _: usize, signal: &Signal) {
+        match signal.pulse {
+            Pulse::Low => self.low += 1,
+            Pulse::High => self.high += 1,
+        }
+    }
+}
+
+/// Remembers the first press on which a module sends a high pulse.
+#[derive(Debug)]
+struct FirstHigh {
+    module: ModuleId,
+    press: Option<usize>,
+}
+
+impl FirstHigh {
+    fn new(module: ModuleId) -> Self {
+        Self {
+            module,
+            press: None,
+        }
+    }
+}
+
+impl Observer for FirstHigh {
+    fn observe(&mut self, press: usize, signal: &Signal) {
+        if signal.from == self.module && signal.pulse == Pulse::High {
+            self.press.get_or_insert(press);
+        }
+    }
+}
+
+/// Finds the period of every input of a conjunction, from the first two presses on which the
+/// input sends a high pulse.
+#[derive(Debug)]
+struct Periods {
+    /// For every input, its first high pulse and the press of the second one.
+    inputs: Vec<(FirstHigh, Option<usize>)>,
+}
+
+impl Periods {
+    fn new(network: &Network, conjunction: ModuleId) -> Self {
+        Self {
+            inputs: network.inputs[conjunction]
+                .iter()
+                .map(|&input| (FirstHigh::new(input), None))
+                .collect(),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.inputs.iter().all(|(_, second)| second.is_some())
+    }
+
+    /// Returns `(offset, period)` for every input, once all periods are known.
+    fn congruences(&self) -> Option<Vec<(i64, i64)>> {
+        self.inputs
+            .iter()
+            .map(|(first, second)| {
+                let (first, second) = (first.press?, (*second)?);
+                let period = (second - first) as i64;
+                Some((first as i64 % period, period))
+            })
+            .collect()
+    }
+}
+
+impl Observer for Periods {
+    fn observe(&mut self, press: usize, signal: &Signal) {
+        for (first, second) in &mut self.inputs {
+            match first.press {
+                None => first.observe(press, signal),
+                Some(at) if at != press && second.is_none() => {
+                    if signal.from == first.module && signal.pulse == Pulse::High {
+                        *second = Some(press);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+/// Writes every pulse of one press, as shown by `--trace`.
+struct Trace<'a, W: Write> {
+    network: &'a Network,
+    press: usize,
+    out: W,
+}
+
+impl<W: Write> Observer for Trace<'_, W> {
+    fn observe(&mut self, press: usize, signal: &Signal) {
+        if press == self.press {
+            writeln!(self.out, "{}", self.network.describe(signal)).unwrap();
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    const PRESSES: usize = 1000;
+
+    let network = Network::parse(input).unwrap();
+    let mut state = network.initial_state();
+    let mut counter = PulseCounter::default();
+    let mut trace = template::is_tracing().then(|| Trace {
+        network: &network,
+        press: 1,
+        out: io::stdout(),
+    });
+
+    for press in 1..=PRESSES {
+        network.press(&mut state, press, &mut (&mut counter, &mut trace));
+    }
+
+    Some(counter.low * counter.high)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    // enough for the feeders of `rx`, which are counters of twelve flip-flops.
+    const MAX_PRESSES: usize = 1 << 14;
+
+    let network = Network::parse(input).unwrap();
+    let rx = network.id("rx")?;
+    // `rx` is fed by a single conjunction, which sends it a low pulse once all of its inputs
+    // sent a high pulse during the same press. Each input does so periodically.
+    let &[feeder] = &network.inputs[rx][..] else {
+        return None;
+    };
+    if network.kinds[feeder] != ModuleKind::Conjunction {
+        return None;
+    }
+
+    let mut state = network.initial_state();
+    let mut periods = Periods::new(&network, feeder);
+    for press in 1..=MAX_PRESSES {
+        network.press(&mut state, press, &mut periods);
+        if periods.is_complete() {
+            break;
+        }
+    }
+
+    let (press, period) = math::crt(&periods.congruences()?)?;
+    Some(if press == 0 { period } else { press } as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two binary counters, which complete after every 4th and every 8th press, starting
+    /// with the 3rd and the 7th.
+    const COUNTERS: &str = "\
+broadcaster -> a1, b1
+%a1 -> a2, ca
+%a2 -> ca
+&ca -> ia
+&ia -> hub
+%b1 -> b2, cb
+%b2 -> b3, cb
+%b3 -> cb
+&cb -> ib
+&ib -> hub
+&hub -> rx";
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file_variant(
+            "examples", PUZZLE, 1,
+        ));
+        assert_eq!(result, Some(32000000));
+
+        let result = part_one(&advent_of_code::template::read_file_variant(
+            "examples", PUZZLE, 2,
+        ));
+        assert_eq!(result, Some(11687500));
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part_two(COUNTERS), Some(7));
+    }
+
+    #[test]
+    fn test_solution_two() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_two(&input);
+        assert_eq!(result, Some(238593356738827));
+    }
+
+    #[test]
+    fn test_periods() {
+        let network = Network::parse(COUNTERS).unwrap();
+        let hub = network.id("hub").unwrap();
+        let mut periods = Periods::new(&network, hub);
+        let mut state = network.initial_state();
+        let mut first_low = None;
+        for press in 1..=16 {
+            let mut trace = Trace {
+                network: &network,
+                press,
+                out: Vec::new(),
+            };
+            network.press(&mut state, press, &mut (&mut periods, &mut trace));
+            if String::from_utf8(trace.out)
+                .unwrap()
+                .contains("hub -low-> rx")
+            {
+                first_low.get_or_insert(press);
+            }
+        }
+        assert_eq!(periods.congruences(), Some(vec![(3, 4), (7, 8)]));
+        // the answer of part two agrees with simulating the presses.
+        assert_eq!(first_low, Some(7));
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = advent_of_code::template::read_file_variant("examples", PUZZLE, 1);
+        let network = Network::parse(&input).unwrap();
+        let mut trace = Trace {
+            network: &network,
+            press: 1,
+            out: Vec::new(),
+        };
+        network.press(&mut network.initial_state(), 1, &mut trace);
+        let expected = "\
+button -low-> broadcaster
+broadcaster -low-> a
+broadcaster -low-> b
+broadcaster -low-> c
+a -high-> b
+b -high-> c
+c -high-> inv
+inv -low-> a
+a -low-> b
+b -low-> c
+c -low-> inv
+inv -high-> a
+";
+        assert_eq!(String::from_utf8(trace.out).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_first_high() {
+        let input = advent_of_code::template::read_file_variant("examples", PUZZLE, 2);
+        let network = Network::parse(&input).unwrap();
+        let mut state = network.initial_state();
+        let mut inverter = FirstHigh::new(network.id("inv").unwrap());
+        let mut conjunction = FirstHigh::new(network.id("con").unwrap());
+        for press in 1..=4 {
+            network.press(&mut state, press, &mut (&mut inverter, &mut conjunction));
+        }
+        assert_eq!(inverter.press, Some(2));
+        assert_eq!(conjunction.press, Some(1));
+    }
+}
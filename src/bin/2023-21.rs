@@ -0,0 +1,143 @@
+use advent_of_code::geometry::Point2;
+use advent_of_code::grid::Grid;
+use advent_of_code::math;
+use advent_of_code::search::{bfs_distances, SearchResult};
+
+advent_of_code::solution!(2023, 21);
+
+/// The most steps the infinite garden is searched for directly.
+const MAX_SEARCHED_STEPS: u64 = 1000;
+
+/// Returns the distance of every garden plot that is reachable from `start` within `max_steps`.
+fn calculate_distances(
+    start: Point2<isize>,
+    max_steps: u64,
+    is_plot: impl Fn(Point2<isize>) -> bool,
+) -> SearchResult<Point2<isize>, usize> {
+    bfs_distances(
+        [start],
+        |pos| pos.neighbors4().into_iter().filter(|&next| is_plot(next)),
+        max_steps as usize,
+    )
+}
+
+/// Counts the plots the elf can end on after exactly `max_steps`, as it can always step back and forth.
+/// The distances may have been searched further than `max_steps`.
+fn count_reachable(distances: &SearchResult<Point2<isize>, usize>, max_steps: u64) -> u64 {
+    distances
+        .distances()
+        .filter(|&(_, steps)| steps as u64 <= max_steps && steps as u64 % 2 == max_steps % 2)
+        .count() as u64
+}
+
+fn parse(input: &str) -> (Grid<char>, Point2<isize>) {
+    let grid: Grid<char> = input.parse().unwrap();
+    let (x, y) = grid.find(&'S').unwrap();
+    (grid, Point2::new(x as isize, y as isize))
+}
+
+/// Counts the plots reachable in exactly `max_steps` within the walls of the garden.
+fn reachable_after(input: &str, max_steps: u64) -> u64 {
+    let (grid, start) = parse(input);
+
+    let destinations = calculate_distances(start, max_steps, |pos| {
+        grid.get_signed(pos.into()).is_some_and(|&c| c != '#')
+    });
+
+    count_reachable(&destinations, max_steps)
+}
+
+/// Counts the plots reachable in exactly `max_steps` in a garden that repeats in every direction,
+/// or [`None`] if the count does not grow quadratically with the copies of the garden
+/// and there are too many steps to search them all.
+///
+/// # Panics
+/// Panics if the garden is not square.
+fn reachable_in_infinite_garden(input: &str, max_steps: u64) -> Option<u64> {
+    let (grid, start) = parse(input);
+    let is_plot = |pos: Point2<isize>| *grid.get_wrapping(pos.into()) != '#';
+
+    // the garden repeats infinitely in every direction. Once the reachable area spans a few
+    // copies of it, the number of plots grows quadratically with every further copy.
+    assert_eq!(grid.width(), grid.height(), "the garden must be square");
+    let size = grid.width() as u64;
+    let rem = max_steps % size;
+    let sampled = rem + 3 * size;
+    if max_steps <= sampled {
+        let destinations = calculate_distances(start, max_steps, is_plot);
+        return Some(count_reachable(&destinations, max_steps));
+    }
+
+    let destinations = calculate_distances(start, sampled, is_plot);
+    let samples: Vec<(i64, i64)> = (0..4)
+        .map(|k| {
+            (
+                k,
+                count_reachable(&destinations, rem + k as u64 * size) as i64,
+            )
+        })
+        .collect();
+    let quadratic = math::to_i64(&math::lagrange(&samples[..3], 3)) == Some(samples[3].1);
+    if !quadratic {
+        // the search covers about 2 * max_steps^2 plots, so it is only an option for few steps.
+        if max_steps > MAX_SEARCHED_STEPS {
+            return None;
+        }
+        let destinations = calculate_distances(start, max_steps, is_plot);
+        return Some(count_reachable(&destinations, max_steps));
+    }
+
+    let copies = (max_steps / size) as i64;
+    math::to_i64(&math::lagrange(&samples[..3], copies)).map(|count| count as u64)
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(reachable_after(input, 64))
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    reachable_in_infinite_garden(input, 26501365)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        assert_eq!(reachable_after(&input, 6), 16);
+    }
+
+    #[test]
+    fn test_solution_one() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        // 64 steps from the center do not reach the edges, so the garden repeating around it
+        // must not change the count.
+        let result = part_one(&input);
+        assert!(result.is_some_and(|count| count > 0));
+        assert_eq!(result, reachable_in_infinite_garden(&input, 64));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        assert_eq!(reachable_in_infinite_garden(&input, 6), Some(16));
+        assert_eq!(reachable_in_infinite_garden(&input, 10), Some(50));
+        assert_eq!(reachable_in_infinite_garden(&input, 50), Some(1594));
+        assert_eq!(reachable_in_infinite_garden(&input, 500), Some(167004));
+        // the example does not grow quadratically, and searching this far would never finish.
+        assert_eq!(reachable_in_infinite_garden(&input, 5000), None);
+    }
+
+    #[test]
+    fn test_solution_two() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_two(&input);
+        assert_eq!(result, Some(621494544278648));
+    }
+}
@@ -1,14 +1,11 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    str::FromStr,
-};
+use std::str::FromStr;
 
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::prelude::*;
 use cgmath::Point3;
 use geo::Intersects;
-use itertools::Itertools;
-use rayon::prelude::*;
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(2023, 22);
 
 struct Brick {
     id: u32,
@@ -50,27 +47,14 @@ impl Intersects for Brick {
 }
 
 impl FromStr for Brick {
-    type Err = ();
+    type Err = ParseError;
 
     // Parse from schema: x,y,z~x,y,z
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split('~');
-        let (x, y, z) = parts
-            .next()
-            .unwrap()
-            .split(',')
-            .map(|x| x.parse().unwrap())
-            .collect_tuple()
-            .unwrap();
+        let (start, end) = parse::split_once(s, "~")?;
+        let [x, y, z] = parse::ints_n(start)?;
         let start = Point3::new(x, y, z);
-
-        let (x, y, z) = parts
-            .next()
-            .unwrap()
-            .split(',')
-            .map(|x| x.parse().unwrap())
-            .collect_tuple()
-            .unwrap();
+        let [x, y, z] = parse::ints_n(end)?;
         let end = Point3::new(x, y, z);
 
         Ok(Brick { id: 0, start, end })
@@ -78,21 +62,16 @@ impl FromStr for Brick {
 }
 
 fn find_supports(input: &str) -> (Vec<Brick>, HashMap<u32, Vec<u32>>, HashMap<u32, Vec<u32>>) {
-    let mut falling_bricks = input
-        .lines()
-        .enumerate()
-        .map(|(i, x)| {
-            let mut brick = x.parse::<Brick>().unwrap();
-            brick.id = i as u32;
-            brick
-        })
-        .collect_vec();
+    let mut falling_bricks = parse::lines(input, str::parse::<Brick>).unwrap();
+    for (i, brick) in falling_bricks.iter_mut().enumerate() {
+        brick.id = i as u32;
+    }
     falling_bricks.sort_by_key(|brick| brick.min_z());
 
     let mut static_bricks: Vec<Brick> = vec![];
 
-    let mut supports = HashMap::new();
-    let mut supported_by = HashMap::new();
+    let mut supports = HashMap::default();
+    let mut supported_by = HashMap::default();
 
     for mut brick in falling_bricks {
         let mut supp = Vec::new();
@@ -154,7 +133,7 @@ fn number_of_drops(
 ) -> u32 {
     let mut heads = VecDeque::new();
     heads.push_back(brick);
-    let mut removed = HashSet::new();
+    let mut removed = HashSet::default();
     removed.insert(brick);
 
     let mut start = true;
@@ -195,13 +174,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(5));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(7));
     }
 }
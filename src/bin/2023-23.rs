@@ -0,0 +1,107 @@
+use advent_of_code::geometry::Dir4;
+use advent_of_code::grid::Grid;
+use advent_of_code::maze::JunctionGraph;
+use advent_of_code::template::viz_dir;
+use advent_of_code::viz::{Image, Rgb};
+
+advent_of_code::solution!(2023, 23);
+
+type Coord = (usize, usize);
+
+/// Returns the longest hike from the top left to the bottom right that never steps on a tile
+/// twice, with every tile of it including the start.
+fn find_longest_path(grid: &Grid<char>, is_slippery: bool) -> Option<Vec<Coord>> {
+    let start = (1, 0);
+    let target = (grid.width() - 2, grid.height() - 1);
+
+    let maze = JunctionGraph::from_grid(
+        grid,
+        &[start, target],
+        |&tile| tile != '#',
+        |&tile| Dir4::try_from(tile).ok().filter(|_| is_slippery),
+    );
+    let (_, junctions) = maze.longest_path(0, 1)?;
+    Some(maze.cells(&junctions))
+}
+
+/// Writes a picture of the hike, from blue at the start to red at the end, if asked to.
+fn save_hike(grid: &Grid<char>, path: &[Coord], name: &str) {
+    let Some(dir) = viz_dir() else {
+        return;
+    };
+    let mut image = Image::from_grid(grid, |&tile| match tile {
+        '#' => Rgb::GREY,
+        _ => Rgb::BLACK,
+    });
+    for (i, &coord) in path.iter().enumerate() {
+        let progress = i as f64 / (path.len().max(2) - 1) as f64;
+        image.set(coord, Rgb::BLUE.mix(Rgb::RED, progress));
+    }
+    let file = dir.join(format!("{PUZZLE}-{name}.png"));
+    image.scaled(4).save(file).unwrap();
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().unwrap();
+    let path = find_longest_path(&grid, true)?;
+    save_hike(&grid, &path, "slippery-hike");
+    Some(path.len() as u32 - 1)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().unwrap();
+    let path = find_longest_path(&grid, false)?;
+    save_hike(&grid, &path, "dry-hike");
+    Some(path.len() as u32 - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Marks the path on a copy of the grid.
+    fn overlay_path(grid: &Grid<char>, path: &[Coord]) -> Grid<char> {
+        let mut grid = grid.clone();
+        for &coord in path {
+            grid[coord] = 'O';
+        }
+        grid
+    }
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(94));
+
+        let grid: Grid<char> = advent_of_code::template::read_file("examples", PUZZLE)
+            .parse()
+            .unwrap();
+        let path = find_longest_path(&grid, true).unwrap();
+        let hike = overlay_path(&grid, &path);
+        assert_eq!(hike.iter().filter(|&&tile| tile == 'O').count(), 95);
+    }
+
+    #[test]
+    fn test_solution_one() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_one(&input);
+        assert_eq!(result, Some(2130));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(154));
+    }
+
+    #[test]
+    fn test_solution_two() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_two(&input);
+        assert_eq!(result, Some(6710));
+    }
+}
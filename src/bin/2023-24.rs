@@ -0,0 +1,174 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use advent_of_code::linalg::{rational, Matrix};
+use advent_of_code::math;
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::prelude::*;
+use cgmath::{vec3, Vector3};
+use num::{BigRational, Signed};
+
+advent_of_code::solution!(2023, 24);
+
+/// The area of the xy plane in which the paths of the hailstones should cross.
+const TEST_AREA: RangeInclusive<i128> = 200_000_000_000_000..=400_000_000_000_000;
+
+struct Hailstone {
+    pos: Vector3<i128>,
+    vel: Vector3<i128>,
+}
+
+impl FromStr for Hailstone {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pos, vel) = parse::split_once(s, "@")?;
+        let [x, y, z] = parse::ints_n(pos)?;
+        let pos = vec3(x, y, z);
+        let [x, y, z] = parse::ints_n(vel)?;
+        let vel = vec3(x, y, z);
+
+        Ok(Self { pos, vel })
+    }
+}
+
+impl Hailstone {
+    /// Returns where the paths of both hailstones cross in the xy plane,
+    /// if both reach that point in the future.
+    fn crossing_xy(&self, other: &Self) -> Option<(BigRational, BigRational)> {
+        // solve pos + t * vel = other.pos + s * other.vel for the times t and s.
+        // Parallel paths have no unique solution.
+        let a = Matrix::from_rows([[self.vel.x, -other.vel.x], [self.vel.y, -other.vel.y]]);
+        let b = [
+            rational(other.pos.x - self.pos.x),
+            rational(other.pos.y - self.pos.y),
+        ];
+        let times = a.solve(&b).ok()?;
+        if times.iter().any(Signed::is_negative) {
+            return None;
+        }
+
+        let t = &times[0];
+        Some((
+            rational(self.pos.x) + t * rational(self.vel.x),
+            rational(self.pos.y) + t * rational(self.vel.y),
+        ))
+    }
+}
+
+fn cross(a: Vector3<i128>, b: Vector3<i128>) -> Vector3<i128> {
+    vec3(
+        a.y * b.z - a.z * b.y,
+        a.z * b.x - a.x * b.z,
+        a.x * b.y - a.y * b.x,
+    )
+}
+
+/// Counts the pairs of hailstones whose paths cross inside the area, bounds included.
+fn count_crossings(hailstones: &[Hailstone], area: RangeInclusive<i128>) -> usize {
+    let (min, max) = (rational(*area.start()), rational(*area.end()));
+    let inside = |value: &BigRational| &min <= value && value <= &max;
+
+    hailstones
+        .par_iter()
+        .enumerate()
+        .map(|(i, a)| {
+            hailstones[i + 1..]
+                .iter()
+                .filter_map(|b| a.crossing_xy(b))
+                .filter(|(x, y)| inside(x) && inside(y))
+                .count()
+        })
+        .sum()
+}
+
+/// Finds the position and velocity of a rock that hits every hailstone.
+///
+/// The rock hits hailstone `i` iff `(P - p_i) × (V - v_i) = 0`. All of these equations share
+/// the only non-linear term `P × V`, so subtracting them pairwise leaves the linear system
+/// `P × (v_j - v_i) + (p_j - p_i) × V = p_j × v_j - p_i × v_i`.
+fn throw_rock(hailstones: &[Hailstone]) -> Option<(Vector3<i128>, Vector3<i128>)> {
+    let (first, others) = hailstones.split_first()?;
+
+    let mut a = Matrix::zeros(0, 6);
+    let mut b = vec![];
+    for other in others {
+        let w = other.vel - first.vel;
+        let d = other.pos - first.pos;
+        let rhs = cross(other.pos, other.vel) - cross(first.pos, first.vel);
+
+        // the unknowns are P.x, P.y, P.z, V.x, V.y, V.z.
+        let rows = [
+            ([0, w.z, -w.y, 0, -d.z, d.y], rhs.x),
+            ([-w.z, 0, w.x, d.z, 0, -d.x], rhs.y),
+            ([w.y, -w.x, 0, -d.y, d.x, 0], rhs.z),
+        ];
+        for (row, rhs) in rows {
+            a.push_row(row.into_iter().map(rational));
+            b.push(rational(rhs));
+        }
+    }
+
+    let solution = a.solve(&b).ok()?;
+    let [x, y, z, vx, vy, vz] = solution
+        .iter()
+        .map(|value| math::to_i64(value).map(i128::from))
+        .collect::<Option<Vec<_>>>()?
+        .try_into()
+        .ok()?;
+    Some((vec3(x, y, z), vec3(vx, vy, vz)))
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let hailstones = parse::lines(input, str::parse::<Hailstone>).unwrap();
+    Some(count_crossings(&hailstones, TEST_AREA) as u32)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let hailstones = parse::lines(input, str::parse::<Hailstone>).unwrap();
+    let (pos, _) = throw_rock(&hailstones)?;
+    u64::try_from(pos.x + pos.y + pos.z).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let hailstones = parse::lines(&input, str::parse::<Hailstone>).unwrap();
+        assert_eq!(count_crossings(&hailstones, 7..=27), 2);
+    }
+
+    #[test]
+    fn test_solution_one() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_one(&input);
+        assert_eq!(result, Some(17244));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let hailstones = parse::lines(&input, str::parse::<Hailstone>).unwrap();
+        assert_eq!(
+            throw_rock(&hailstones),
+            Some((vec3(24, 13, 10), vec3(-3, 1, 2)))
+        );
+
+        let result = part_two(&input);
+        assert_eq!(result, Some(47));
+    }
+
+    #[test]
+    fn test_solution_two() {
+        let Some(input) = advent_of_code::template::read_input_or_skip(PUZZLE) else {
+            return;
+        };
+        let result = part_two(&input);
+        assert_eq!(result, Some(1025019997186820));
+    }
+}
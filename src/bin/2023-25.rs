@@ -0,0 +1,58 @@
+use advent_of_code::graph::{self, LabelledUnGraph};
+use advent_of_code::parse;
+
+advent_of_code::solution!(2023, 25);
+
+fn parse_wiring(input: &str) -> LabelledUnGraph {
+    LabelledUnGraph::from_lines(input, |line| {
+        let (component, connected) = parse::key_value(line, ":")?;
+        Ok((component, connected.split_whitespace().collect()))
+    })
+    .unwrap()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let wiring = parse_wiring(input);
+
+    // the components fall apart into two groups when the three wires of the minimal cut are removed.
+    let cut = graph::min_cut(wiring.graph(), |_| 1)?;
+    let (left, right) = cut.partition;
+
+    Some((left.len() * right.len()) as u32)
+}
+
+pub fn part_two(_input: &str) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(54));
+
+        let wiring = parse_wiring(&advent_of_code::template::read_file("examples", PUZZLE));
+        let cut = graph::min_cut(wiring.graph(), |_| 1).unwrap();
+        let mut wires = cut
+            .edges
+            .iter()
+            .map(|&edge| {
+                let (a, b) = wiring.graph().edge_endpoints(edge).unwrap();
+                let mut wire = [wiring.label(a), wiring.label(b)];
+                wire.sort_unstable();
+                wire
+            })
+            .collect::<Vec<_>>();
+        wires.sort_unstable();
+        assert_eq!(wires, [["bvb", "cmg"], ["hfx", "pzl"], ["jqt", "nvd"]]);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+}
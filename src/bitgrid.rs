@@ -0,0 +1,420 @@
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use crate::geometry::Dir4;
+use crate::grid::Grid;
+
+/// The widest row a [`BitGrid`] can hold.
+pub const MAX_WIDTH: usize = u128::BITS as usize;
+
+/// A grid of booleans with every row packed into a single `u128`.
+///
+/// Bit `x` of a row is the cell in column `x`, so whole rows are set, shifted and counted at
+/// once. Grids compare cell by cell, but hash as their [`BitGrid::state_hash`], which makes
+/// them cheap to remember in cycle detection.
+///
+/// ```
+/// # use advent_of_code::bitgrid::BitGrid;
+/// # use advent_of_code::geometry::Dir4;
+/// # use advent_of_code::grid::Grid;
+/// let grid: Grid<char> = "#..\n.#.\n...".parse().unwrap();
+/// let walls = BitGrid::from_grid(&grid, |&c| c == '#');
+/// assert_eq!(walls.count_ones(), 2);
+/// assert!(walls.shift(Dir4::East).get((2, 1)));
+/// assert_eq!(walls.shift(Dir4::North).count_ones(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    rows: Vec<u128>,
+}
+
+impl BitGrid {
+    /// Creates an empty grid.
+    ///
+    /// # Panics
+    /// Panics if `width` exceeds [`MAX_WIDTH`].
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(
+            width <= MAX_WIDTH,
+            "a bit grid is at most {MAX_WIDTH} cells wide, got {width}"
+        );
+        Self {
+            width,
+            height,
+            rows: vec![0; height],
+        }
+    }
+
+    /// Creates a grid with the cells of `grid` for which `predicate` holds set.
+    ///
+    /// # Panics
+    /// Like [`BitGrid::new`].
+    #[must_use]
+    pub fn from_grid<T>(grid: &Grid<T>, mut predicate: impl FnMut(&T) -> bool) -> Self {
+        let mut bits = Self::new(grid.width(), grid.height());
+        for (y, row) in grid.rows().enumerate() {
+            bits.rows[y] = row
+                .iter()
+                .enumerate()
+                .filter(|(_, cell)| predicate(cell))
+                .fold(0, |word, (x, _)| word | 1 << x);
+        }
+        bits
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the bits of row `y`.
+    #[must_use]
+    pub fn row(&self, y: usize) -> u128 {
+        self.rows[y]
+    }
+
+    /// Returns all rows, top to bottom.
+    #[must_use]
+    pub fn rows(&self) -> &[u128] {
+        &self.rows
+    }
+
+    /// Returns all rows for bulk updates. Bits beyond the width have to stay clear.
+    #[must_use]
+    pub fn rows_mut(&mut self) -> &mut [u128] {
+        &mut self.rows
+    }
+
+    /// Returns whether the cell is set. Cells outside the grid are never set.
+    #[must_use]
+    pub fn get(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height && self.rows[y] >> x & 1 == 1
+    }
+
+    /// Sets or clears the cell, returning whether it was set before.
+    ///
+    /// # Panics
+    /// Panics if the cell is outside the grid.
+    pub fn set(&mut self, (x, y): (usize, usize), value: bool) -> bool {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is out of bounds"
+        );
+        let was_set = self.rows[y] >> x & 1 == 1;
+        if value {
+            self.rows[y] |= 1 << x;
+        } else {
+            self.rows[y] &= !(1 << x);
+        }
+        was_set
+    }
+
+    /// Returns the number of set cells.
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.rows.iter().map(|row| row.count_ones() as usize).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(|&row| row == 0)
+    }
+
+    /// Iterates the positions of the set cells in row-major order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.rows.iter().enumerate().flat_map(|(y, &row)| {
+            let mut rest = row;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let x = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some((x, y))
+            })
+        })
+    }
+
+    /// Moves every cell one step in the direction. Cells moved off the grid are dropped.
+    #[must_use]
+    pub fn shift(&self, dir: Dir4) -> Self {
+        let mut shifted = Self::new(self.width, self.height);
+        match dir {
+            Dir4::North => shifted.rows[..self.height.saturating_sub(1)]
+                .copy_from_slice(&self.rows[self.height.min(1)..]),
+            Dir4::South => shifted.rows[self.height.min(1)..]
+                .copy_from_slice(&self.rows[..self.height.saturating_sub(1)]),
+            Dir4::East => {
+                let mask = self.mask();
+                for (to, from) in shifted.rows.iter_mut().zip(&self.rows) {
+                    *to = from << 1 & mask;
+                }
+            }
+            Dir4::West => {
+                for (to, from) in shifted.rows.iter_mut().zip(&self.rows) {
+                    *to = from >> 1;
+                }
+            }
+        }
+        shifted
+    }
+
+    /// Mirrors the grid along its main diagonal.
+    ///
+    /// # Panics
+    /// Panics if the grid is higher than [`MAX_WIDTH`].
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let mut transposed = Self::new(self.height, self.width);
+        for (x, y) in self.iter_ones() {
+            transposed.rows[x] |= 1 << y;
+        }
+        transposed
+    }
+
+    /// Mirrors the grid left to right.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        let unused = MAX_WIDTH - self.width;
+        Self {
+            rows: self
+                .rows
+                .iter()
+                .map(|row| row.reverse_bits().checked_shr(unused as u32).unwrap_or(0))
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Mirrors the grid top to bottom.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        Self {
+            rows: self.rows.iter().rev().copied().collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Rotates the grid by a quarter turn clockwise.
+    ///
+    /// # Panics
+    /// Like [`BitGrid::transpose`].
+    #[must_use]
+    pub fn rotate_cw(&self) -> Self {
+        self.flip_vertical().transpose()
+    }
+
+    /// Rotates the grid by a quarter turn counter-clockwise.
+    ///
+    /// # Panics
+    /// Like [`BitGrid::transpose`].
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Self {
+        self.transpose().flip_vertical()
+    }
+
+    /// Returns a 64-bit hash of the cells. See [`state_hash`].
+    #[must_use]
+    pub fn state_hash(&self) -> u64 {
+        #[allow(clippy::cast_possible_truncation)]
+        state_hash(
+            self.rows
+                .iter()
+                .flat_map(|&row| [row as u64, (row >> 64) as u64]),
+        )
+    }
+
+    /// Returns a row with the bits of all columns set.
+    fn mask(&self) -> u128 {
+        u128::MAX
+            .checked_shr((MAX_WIDTH - self.width) as u32)
+            .unwrap_or(0)
+    }
+
+    fn zip_rows(&self, other: &Self, f: impl Fn(u128, u128) -> u128) -> Self {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "bit grids must have the same size"
+        );
+        Self {
+            rows: self
+                .rows
+                .iter()
+                .zip(&other.rows)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Hashes a sequence of words into 64 bits.
+///
+/// A multiply-rotate hash in the style of `FxHash` with a final avalanche, so it is much
+/// cheaper than the standard library's `SipHash`. It is not resistant to crafted collisions,
+/// which makes it fit for simulation states but not for untrusted input.
+///
+/// ```
+/// # use advent_of_code::bitgrid::state_hash;
+/// assert_eq!(state_hash([1, 2, 3]), state_hash([1, 2, 3]));
+/// assert_ne!(state_hash([1, 2, 3]), state_hash([3, 2, 1]));
+/// ```
+#[must_use]
+pub fn state_hash(words: impl IntoIterator<Item = u64>) -> u64 {
+    const SEED: u64 = 0x517c_c1b7_2722_0a95;
+
+    let mut hash = words.into_iter().fold(0, |hash: u64, word| {
+        (hash.rotate_left(5) ^ word).wrapping_mul(SEED)
+    });
+    // the finalizer of splitmix64, so that every input bit affects every output bit.
+    hash ^= hash >> 30;
+    hash = hash.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash ^= hash >> 27;
+    hash = hash.wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ hash >> 31
+}
+
+/// Feeds only the [`BitGrid::state_hash`] to the hasher.
+impl Hash for BitGrid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.state_hash());
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.zip_rows(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.zip_rows(rhs, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.zip_rows(rhs, |a, b| a ^ b)
+    }
+}
+
+/// Flips every cell inside the grid.
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> Self::Output {
+        let mask = self.mask();
+        BitGrid {
+            rows: self.rows.iter().map(|row| !row & mask).collect(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Prints set cells as `#` and the others as `.`.
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows.iter().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                write!(f, "{}", if row >> x & 1 == 1 { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bits: &BitGrid) -> Self {
+        Grid::from_fn(bits.width, bits.height, |pos| bits.get(pos))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BitGrid;
+    use crate::geometry::Dir4;
+    use crate::grid::Grid;
+
+    fn bits(s: &str) -> BitGrid {
+        let grid: Grid<char> = s.parse().unwrap();
+        BitGrid::from_grid(&grid, |&c| c == '#')
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        let s = "#..#\n.##.\n...#";
+        let grid = bits(s);
+        assert_eq!(grid.to_string(), s);
+        assert_eq!(grid.count_ones(), 5);
+        assert_eq!(
+            grid.iter_ones().collect::<Vec<_>>(),
+            [(0, 0), (3, 0), (1, 1), (2, 1), (3, 2)]
+        );
+        assert_eq!(Grid::from(&grid).find(&true), Some((0, 0)));
+    }
+
+    #[test]
+    fn shifts_and_drops_cells() {
+        let grid = bits("#..#\n.##.\n...#");
+        assert_eq!(grid.shift(Dir4::East).to_string(), ".#..\n..##\n....");
+        assert_eq!(grid.shift(Dir4::West).to_string(), "..#.\n##..\n..#.");
+        assert_eq!(grid.shift(Dir4::North).to_string(), ".##.\n...#\n....");
+        assert_eq!(grid.shift(Dir4::South).to_string(), "....\n#..#\n.##.");
+    }
+
+    #[test]
+    fn rotates_like_grid() {
+        let s = "##.\n..#\n#..\n.#.";
+        let grid: Grid<char> = s.parse().unwrap();
+        let to_bits = |grid: &Grid<char>| BitGrid::from_grid(grid, |&c| c == '#');
+        let bits = to_bits(&grid);
+        assert_eq!(bits.transpose(), to_bits(&grid.transpose()));
+        assert_eq!(bits.rotate_cw(), to_bits(&grid.rotate_cw()));
+        assert_eq!(bits.rotate_ccw(), to_bits(&grid.rotate_ccw()));
+        assert_eq!(bits.flip_horizontal(), to_bits(&grid.flip_horizontal()));
+        assert_eq!(bits.flip_vertical(), to_bits(&grid.flip_vertical()));
+    }
+
+    #[test]
+    fn supports_full_width_rows() {
+        let mut grid = BitGrid::new(128, 2);
+        assert!(!grid.set((127, 1), true));
+        assert!(grid.set((127, 1), true));
+        assert_eq!(grid.shift(Dir4::East).count_ones(), 0);
+        assert!(grid.flip_horizontal().get((0, 1)));
+        assert_eq!((!&grid).count_ones(), 255);
+    }
+
+    #[test]
+    fn combines_and_hashes() {
+        let a = bits("##.\n...");
+        let b = bits(".##\n..#");
+        assert_eq!((&a & &b).to_string(), ".#.\n...");
+        assert_eq!((&a | &b).to_string(), "###\n..#");
+        assert_eq!((&a ^ &b).to_string(), "#.#\n..#");
+        assert_eq!((!&a).to_string(), "..#\n###");
+
+        assert_eq!(a.state_hash(), bits("##.\n...").state_hash());
+        assert_ne!(a.state_hash(), b.state_hash());
+        assert_ne!(a.state_hash(), a.shift(Dir4::South).state_hash());
+    }
+}
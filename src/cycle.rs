@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in the sequence `x, f(x), f(f(x)), ...`. After the first `prefix` states,
+/// the sequence repeats every `period` states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Maps a step count onto the earliest step that has the same state.
+    ///
+    /// ```
+    /// # use advent_of_code::cycle::Cycle;
+    /// let cycle = Cycle { prefix: 2, period: 3 };
+    /// assert_eq!(cycle.reduce(1), 1);
+    /// assert_eq!(cycle.reduce(5), 2);
+    /// assert_eq!(cycle.reduce(1_000_000_000), 4);
+    /// ```
+    #[must_use]
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.period
+        }
+    }
+}
+
+/// The states of a sequence, recorded until they start repeating.
+#[derive(Debug, Clone)]
+pub struct History<T> {
+    cycle: Option<Cycle>,
+    states: Vec<T>,
+}
+
+impl<T> History<T> {
+    /// Returns the cycle, or [`None`] if recording stopped before the states repeated.
+    #[must_use]
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    /// Returns the recorded states, starting with the initial one.
+    /// With a cycle, these are the first `prefix + period` states.
+    #[must_use]
+    pub fn states(&self) -> &[T] {
+        &self.states
+    }
+
+    /// Returns the state after `n` steps.
+    ///
+    /// # Panics
+    /// Panics if no cycle was found and the state after `n` steps was not recorded.
+    #[must_use]
+    pub fn nth(&self, n: usize) -> &T {
+        match self.cycle {
+            Some(cycle) => &self.states[cycle.reduce(n)],
+            None => self
+                .states
+                .get(n)
+                .unwrap_or_else(|| panic!("the state after {n} steps was not recorded")),
+        }
+    }
+
+    /// Iterates the states after `0..n` steps, without computing any new ones.
+    ///
+    /// # Panics
+    /// Like [`History::nth`].
+    pub fn take(&self, n: usize) -> impl Iterator<Item = &T> {
+        (0..n).map(|i| self.nth(i))
+    }
+}
+
+/// Finds the cycle by remembering every state. Fast, and keeps the states around,
+/// but needs memory for all of them.
+pub fn detect<T: Hash + Eq + Clone>(start: T, f: impl FnMut(&T) -> T) -> History<T> {
+    record(start, f, usize::MAX)
+}
+
+/// Like [`detect`], but stops after `limit` steps. Useful when only the first states are
+/// needed and the cycle may be much longer.
+pub fn record<T: Hash + Eq + Clone>(
+    start: T,
+    mut f: impl FnMut(&T) -> T,
+    limit: usize,
+) -> History<T> {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut current = start;
+
+    loop {
+        if let Some(&prefix) = seen.get(&current) {
+            let period = states.len() - prefix;
+            return History {
+                cycle: Some(Cycle { prefix, period }),
+                states,
+            };
+        }
+        if states.len() == limit {
+            states.push(current);
+            return History {
+                cycle: None,
+                states,
+            };
+        }
+
+        let next = f(&current);
+        seen.insert(current.clone(), states.len());
+        states.push(current);
+        current = next;
+    }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare, using constant memory.
+pub fn floyd<T: Eq + Clone>(start: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = f(&start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let ahead = f(&hare);
+        hare = f(&ahead);
+    }
+
+    // the distance from the start to the meeting point is a multiple of the period.
+    let mut prefix = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    let mut period = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        period += 1;
+    }
+
+    Cycle { prefix, period }
+}
+
+/// Finds the cycle with Brent's algorithm, using constant memory and fewer calls of `f` than [`floyd`].
+pub fn brent<T: Eq + Clone>(start: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    // move the hare one period ahead, then advance both until they meet at the start of the cycle.
+    tortoise = start.clone();
+    hare = advance(start, &mut f, period);
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    Cycle { prefix, period }
+}
+
+/// Returns the state after `n` steps. Combine with [`Cycle::reduce`] for large `n`.
+pub fn advance<T>(start: T, mut f: impl FnMut(&T) -> T, n: usize) -> T {
+    (0..n).fold(start, |state, _| f(&state))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{advance, brent, detect, floyd, record, Cycle};
+
+    /// 0, 1, ..., 6, 3, 4, 5, 6, 3, ...
+    fn step(n: &u32) -> u32 {
+        if *n == 6 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    const CYCLE: Cycle = Cycle {
+        prefix: 3,
+        period: 4,
+    };
+
+    #[test]
+    fn detects_with_hashing() {
+        let history = detect(0, step);
+        assert_eq!(history.cycle(), Some(CYCLE));
+        assert_eq!(history.states(), [0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(*history.nth(2), 2);
+        assert_eq!(*history.nth(7), 3);
+        assert_eq!(
+            *history.nth(1_000_000_000),
+            advance(0, step, CYCLE.reduce(1_000_000_000))
+        );
+        assert_eq!(
+            history.take(10).copied().collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 5, 6, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn detects_with_floyd() {
+        assert_eq!(floyd(0, step), CYCLE);
+    }
+
+    #[test]
+    fn detects_with_brent() {
+        assert_eq!(brent(0, step), CYCLE);
+    }
+
+    #[test]
+    fn detects_pure_cycles() {
+        let step = |n: &u32| (n + 1) % 5;
+        let expected = Cycle {
+            prefix: 0,
+            period: 5,
+        };
+        assert_eq!(detect(0, step).cycle().unwrap(), expected);
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+    }
+
+    #[test]
+    fn detects_fixed_points() {
+        let step = |n: &u32| (n + 1).min(4);
+        let expected = Cycle {
+            prefix: 4,
+            period: 1,
+        };
+        assert_eq!(detect(0, step).cycle().unwrap(), expected);
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+    }
+
+    #[test]
+    fn agrees_on_long_sequences() {
+        // the pseudo random sequence of Pollard's rho has a long prefix and period.
+        let step = |n: &u64| (n * n + 1) % 10_007;
+        let cycle = detect(2, step).cycle().unwrap();
+        assert_eq!(floyd(2, step), cycle);
+        assert_eq!(brent(2, step), cycle);
+        assert_eq!(
+            advance(2, step, cycle.prefix),
+            advance(2, step, cycle.prefix + cycle.period)
+        );
+    }
+
+    #[test]
+    fn records_up_to_limit() {
+        let history = record(0, |n: &u32| n + 1, 3);
+        assert_eq!(history.cycle(), None);
+        assert_eq!(history.states(), [0, 1, 2, 3]);
+        assert_eq!(*history.nth(3), 3);
+
+        let history = record(0, step, 100);
+        assert_eq!(history.cycle(), Some(CYCLE));
+        assert_eq!(*history.nth(100), advance(0, step, 100));
+    }
+
+    #[test]
+    #[should_panic(expected = "not recorded")]
+    fn panics_beyond_limit() {
+        let _ = record(0, |n: &u32| n + 1, 3).nth(4);
+    }
+
+    #[test]
+    fn reduces_steps() {
+        assert_eq!(CYCLE.reduce(0), 0);
+        assert_eq!(CYCLE.reduce(3), 3);
+        assert_eq!(CYCLE.reduce(7), 3);
+        assert_eq!(CYCLE.reduce(10), 6);
+    }
+}
@@ -0,0 +1,613 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use num::{Signed, Zero};
+
+use crate::grid::Grid;
+
+/// A point or vector in two dimensions. Like [`Grid`], `y` grows downwards.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Zero> Point2<T> {
+    #[must_use]
+    pub fn origin() -> Self {
+        Self::new(T::zero(), T::zero())
+    }
+}
+
+impl<T: Copy + PartialOrd + Sub<Output = T> + Add<Output = T>> Point2<T> {
+    /// Returns the taxicab distance, i.e. the number of orthogonal steps between the points.
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Returns the chessboard distance, i.e. the number of king moves between the points.
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+impl<T: Signed + Copy> Point2<T> {
+    /// Returns the point moved one step in a direction.
+    #[must_use]
+    pub fn step(self, dir: impl Into<Dir8>) -> Self {
+        self + dir.into().offset()
+    }
+
+    /// Returns the point moved `n` steps in a direction.
+    #[must_use]
+    pub fn step_n(self, dir: impl Into<Dir8>, n: T) -> Self {
+        self + dir.into().offset() * n
+    }
+
+    /// Returns the orthogonal neighbours, in the order of [`Dir4::ALL`].
+    #[must_use]
+    pub fn neighbors4(self) -> [Self; 4] {
+        Dir4::ALL.map(|dir| self.step(dir))
+    }
+
+    /// Returns all eight neighbours, in the order of [`Dir8::ALL`].
+    #[must_use]
+    pub fn neighbors8(self) -> [Self; 8] {
+        Dir8::ALL.map(|dir| self.step(dir))
+    }
+}
+
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T: geo::CoordNum> From<geo::Coord<T>> for Point2<T> {
+    fn from(c: geo::Coord<T>) -> Self {
+        Self::new(c.x, c.y)
+    }
+}
+
+impl<T: geo::CoordNum> From<Point2<T>> for geo::Coord<T> {
+    fn from(p: Point2<T>) -> Self {
+        geo::Coord { x: p.x, y: p.y }
+    }
+}
+
+impl<T> From<cgmath::Point2<T>> for Point2<T> {
+    fn from(p: cgmath::Point2<T>) -> Self {
+        Self::new(p.x, p.y)
+    }
+}
+
+impl<T> From<Point2<T>> for cgmath::Point2<T> {
+    fn from(p: Point2<T>) -> Self {
+        cgmath::Point2::new(p.x, p.y)
+    }
+}
+
+impl<T> From<cgmath::Vector2<T>> for Point2<T> {
+    fn from(v: cgmath::Vector2<T>) -> Self {
+        Self::new(v.x, v.y)
+    }
+}
+
+impl<T> From<Point2<T>> for cgmath::Vector2<T> {
+    fn from(p: Point2<T>) -> Self {
+        cgmath::Vector2::new(p.x, p.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A change of direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Straight,
+    Right,
+    Back,
+    Left,
+}
+
+impl Turn {
+    /// Number of clockwise quarter turns.
+    const fn quarters(self) -> u8 {
+        match self {
+            Turn::Straight => 0,
+            Turn::Right => 1,
+            Turn::Back => 2,
+            Turn::Left => 3,
+        }
+    }
+}
+
+/// One of the four orthogonal directions. North points up, towards smaller `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Dir4 {
+    /// All directions, clockwise starting at north.
+    pub const ALL: [Dir4; 4] = [Dir4::North, Dir4::East, Dir4::South, Dir4::West];
+
+    const fn from_index(i: u8) -> Self {
+        Self::ALL[(i % 4) as usize]
+    }
+
+    const fn index(self) -> u8 {
+        self as u8
+    }
+
+    #[must_use]
+    pub const fn turn(self, turn: Turn) -> Self {
+        Self::from_index(self.index() + turn.quarters())
+    }
+
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        self.turn(Turn::Left)
+    }
+
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        self.turn(Turn::Right)
+    }
+
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        self.turn(Turn::Back)
+    }
+
+    /// Returns the turn that leads from this direction to `other`.
+    #[must_use]
+    pub const fn turn_to(self, other: Self) -> Turn {
+        match (other.index() + 4 - self.index()) % 4 {
+            0 => Turn::Straight,
+            1 => Turn::Right,
+            2 => Turn::Back,
+            _ => Turn::Left,
+        }
+    }
+
+    /// Returns the direction after bouncing off a `/` mirror.
+    #[must_use]
+    pub const fn reflect_slash(self) -> Self {
+        match self {
+            Dir4::North => Dir4::East,
+            Dir4::East => Dir4::North,
+            Dir4::South => Dir4::West,
+            Dir4::West => Dir4::South,
+        }
+    }
+
+    /// Returns the direction after bouncing off a `\` mirror.
+    #[must_use]
+    pub const fn reflect_backslash(self) -> Self {
+        match self {
+            Dir4::North => Dir4::West,
+            Dir4::West => Dir4::North,
+            Dir4::South => Dir4::East,
+            Dir4::East => Dir4::South,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_horizontal(self) -> bool {
+        matches!(self, Dir4::East | Dir4::West)
+    }
+
+    #[must_use]
+    pub const fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// Returns the unit vector pointing in this direction.
+    #[must_use]
+    pub fn offset<T: Signed>(self) -> Point2<T> {
+        Dir8::from(self).offset()
+    }
+}
+
+/// Parses `U`/`R`/`D`/`L`, `N`/`E`/`S`/`W` and `^`/`>`/`v`/`<`.
+impl TryFrom<char> for Dir4 {
+    type Error = DirFromCharError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | 'N' | '^' => Ok(Dir4::North),
+            'R' | 'E' | '>' => Ok(Dir4::East),
+            'D' | 'S' | 'v' => Ok(Dir4::South),
+            'L' | 'W' | '<' => Ok(Dir4::West),
+            _ => Err(DirFromCharError(c)),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Dir4`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct DirFromCharError(pub char);
+
+impl Display for DirFromCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a direction.", self.0)
+    }
+}
+
+impl std::error::Error for DirFromCharError {}
+
+/// One of the eight compass directions. North points up, towards smaller `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    /// All directions, clockwise starting at north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    const fn from_index(i: u8) -> Self {
+        Self::ALL[(i % 8) as usize]
+    }
+
+    const fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// Turns by quarter turns, like [`Dir4::turn`].
+    #[must_use]
+    pub const fn turn(self, turn: Turn) -> Self {
+        Self::from_index(self.index() + 2 * turn.quarters())
+    }
+
+    /// Turns by 45° counterclockwise.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 7)
+    }
+
+    /// Turns by 45° clockwise.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        self.turn(Turn::Back)
+    }
+
+    #[must_use]
+    pub const fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// Returns the unit vector pointing in this direction. Diagonals have length one on both axes.
+    #[must_use]
+    pub fn offset<T: Signed>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Dir8::North => (0, -1),
+            Dir8::NorthEast => (1, -1),
+            Dir8::East => (1, 0),
+            Dir8::SouthEast => (1, 1),
+            Dir8::South => (0, 1),
+            Dir8::SouthWest => (-1, 1),
+            Dir8::West => (-1, 0),
+            Dir8::NorthWest => (-1, -1),
+        };
+        Point2::new(unit(x), unit(y))
+    }
+}
+
+fn unit<T: Signed>(n: i8) -> T {
+    match n {
+        1 => T::one(),
+        -1 => -T::one(),
+        _ => T::zero(),
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::from_index(dir.index() * 2)
+    }
+}
+
+impl TryFrom<Dir8> for Dir4 {
+    type Error = Dir8;
+
+    /// Fails for diagonals, returning them unchanged.
+    fn try_from(dir: Dir8) -> Result<Self, Self::Error> {
+        if dir.is_diagonal() {
+            Err(dir)
+        } else {
+            Ok(Self::from_index(dir.index() / 2))
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extension trait to index two-dimensional containers by [`Point2`].
+/// Points outside the container, including negative ones, yield [`None`].
+pub trait PointIndex<T> {
+    fn at<P: TryIntoIndex>(&self, p: Point2<P>) -> Option<&T>;
+
+    fn at_mut<P: TryIntoIndex>(&mut self, p: Point2<P>) -> Option<&mut T>;
+}
+
+/// Coordinate types that may or may not be valid indices.
+pub trait TryIntoIndex: Copy {
+    fn try_into_index(self) -> Option<usize>;
+}
+
+macro_rules! impl_try_into_index {
+    ($($t:ty),*) => {
+        $(impl TryIntoIndex for $t {
+            fn try_into_index(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
+        })*
+    };
+}
+
+impl_try_into_index!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+fn to_index<P: TryIntoIndex>(p: Point2<P>) -> Option<(usize, usize)> {
+    Some((p.x.try_into_index()?, p.y.try_into_index()?))
+}
+
+/// Rows are indexed by `y`, columns by `x`.
+impl<T> PointIndex<T> for Vec<Vec<T>> {
+    fn at<P: TryIntoIndex>(&self, p: Point2<P>) -> Option<&T> {
+        let (x, y) = to_index(p)?;
+        self.get(y)?.get(x)
+    }
+
+    fn at_mut<P: TryIntoIndex>(&mut self, p: Point2<P>) -> Option<&mut T> {
+        let (x, y) = to_index(p)?;
+        self.get_mut(y)?.get_mut(x)
+    }
+}
+
+impl<T> PointIndex<T> for Grid<T> {
+    fn at<P: TryIntoIndex>(&self, p: Point2<P>) -> Option<&T> {
+        self.get(to_index(p)?)
+    }
+
+    fn at_mut<P: TryIntoIndex>(&mut self, p: Point2<P>) -> Option<&mut T> {
+        self.get_mut(to_index(p)?)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8, DirFromCharError, Point2, PointIndex, Turn};
+    use crate::grid::Grid;
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a + b, Point2::new(-2, 7));
+        assert_eq!(a - b, Point2::new(4, -3));
+        assert_eq!(-a, Point2::new(-1, -2));
+        assert_eq!(a * 3, Point2::new(3, 6));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(Point2::<i32>::origin(), Point2::new(0, 0));
+        assert_eq!(a.to_string(), "(1, 2)");
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Point2::new(3u32, 0).manhattan(Point2::new(1, 4)), 6);
+    }
+
+    #[test]
+    fn steps() {
+        let p = Point2::new(0i64, 0);
+        assert_eq!(p.step(Dir4::North), Point2::new(0, -1));
+        assert_eq!(p.step(Dir8::SouthWest), Point2::new(-1, 1));
+        assert_eq!(p.step_n(Dir4::East, 5), Point2::new(5, 0));
+        assert_eq!(
+            p.neighbors4(),
+            [(0, -1), (1, 0), (0, 1), (-1, 0)].map(Point2::from)
+        );
+        assert_eq!(p.neighbors8()[1], Point2::new(1, -1));
+    }
+
+    #[test]
+    fn turns_dir4() {
+        assert_eq!(Dir4::North.turn_right(), Dir4::East);
+        assert_eq!(Dir4::North.turn_left(), Dir4::West);
+        assert_eq!(Dir4::West.turn_right(), Dir4::North);
+        assert_eq!(Dir4::East.reverse(), Dir4::West);
+        assert_eq!(Dir4::South.turn(Turn::Straight), Dir4::South);
+
+        for a in Dir4::ALL {
+            for b in Dir4::ALL {
+                assert_eq!(a.turn(a.turn_to(b)), b);
+            }
+        }
+    }
+
+    #[test]
+    fn reflects_dir4() {
+        assert_eq!(Dir4::East.reflect_slash(), Dir4::North);
+        assert_eq!(Dir4::South.reflect_slash(), Dir4::West);
+        assert_eq!(Dir4::East.reflect_backslash(), Dir4::South);
+        assert_eq!(Dir4::North.reflect_backslash(), Dir4::West);
+
+        for dir in Dir4::ALL {
+            assert_eq!(dir.reflect_slash().reflect_slash(), dir);
+            assert_eq!(dir.reflect_backslash().reflect_backslash(), dir);
+            assert_ne!(dir.is_horizontal(), dir.is_vertical());
+        }
+    }
+
+    #[test]
+    fn turns_dir8() {
+        assert_eq!(Dir8::North.turn_right(), Dir8::NorthEast);
+        assert_eq!(Dir8::North.turn_left(), Dir8::NorthWest);
+        assert_eq!(Dir8::NorthEast.turn(Turn::Right), Dir8::SouthEast);
+        assert_eq!(Dir8::SouthWest.reverse(), Dir8::NorthEast);
+        assert!(Dir8::SouthWest.is_diagonal());
+        assert!(!Dir8::South.is_diagonal());
+    }
+
+    #[test]
+    fn converts_dirs() {
+        assert_eq!(Dir8::from(Dir4::West), Dir8::West);
+        assert_eq!(Dir4::try_from(Dir8::South), Ok(Dir4::South));
+        assert_eq!(Dir4::try_from(Dir8::SouthEast), Err(Dir8::SouthEast));
+
+        for dir in Dir4::ALL {
+            assert_eq!(dir.offset::<i32>(), Dir8::from(dir).offset());
+        }
+        assert_eq!(Dir8::NorthEast.offset::<i8>(), Point2::new(1, -1));
+    }
+
+    #[test]
+    fn parses_dir4() {
+        assert_eq!(Dir4::try_from('U'), Ok(Dir4::North));
+        assert_eq!(Dir4::try_from('>'), Ok(Dir4::East));
+        assert_eq!(Dir4::try_from('v'), Ok(Dir4::South));
+        assert_eq!(Dir4::try_from('W'), Ok(Dir4::West));
+        assert_eq!(Dir4::try_from('x'), Err(DirFromCharError('x')));
+    }
+
+    #[test]
+    fn converts_points() {
+        let p = Point2::new(3.0, 4.0);
+        let c: geo::Coord<f64> = p.into();
+        assert_eq!(Point2::from(c), p);
+        let c: cgmath::Point2<f64> = p.into();
+        assert_eq!(Point2::from(c), p);
+        let v: cgmath::Vector2<f64> = p.into();
+        assert_eq!(Point2::from(v), p);
+        assert_eq!(<(i32, i32)>::from(Point2::new(1, 2)), (1, 2));
+    }
+
+    #[test]
+    fn indexes_by_point() {
+        let mut rows = vec![vec!['a', 'b'], vec!['c', 'd']];
+        assert_eq!(rows.at(Point2::new(1, 0)), Some(&'b'));
+        assert_eq!(rows.at(Point2::new(0usize, 1)), Some(&'c'));
+        assert_eq!(rows.at(Point2::new(-1, 0)), None);
+        assert_eq!(rows.at(Point2::new(2, 0)), None);
+        *rows.at_mut(Point2::new(1u8, 1)).unwrap() = 'x';
+        assert_eq!(rows[1][1], 'x');
+
+        let grid: Grid<char> = "ab\ncd".parse().unwrap();
+        assert_eq!(grid.at(Point2::new(0i64, 1)), Some(&'c'));
+        assert_eq!(grid.at(Point2::new(0i64, -1)), None);
+    }
+}
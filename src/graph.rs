@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Write};
+
+use indexmap::IndexSet;
+use petgraph::graph::{EdgeIndex, Graph, NodeIndex};
+use petgraph::stable_graph::StableUnGraph;
+use petgraph::unionfind::UnionFind;
+use petgraph::visit::{Bfs, EdgeRef, IntoNodeIdentifiers};
+use petgraph::{Directed, EdgeType, Undirected};
+use priority_queue::PriorityQueue;
+
+use crate::parse::{self, ParseError};
+
+/// A petgraph [`Graph`] whose nodes are identified by string labels.
+///
+/// Labels are interned: every label is stored once, and its position in the set of labels is
+/// the index of its node.
+///
+/// ```
+/// # use advent_of_code::graph::LabelledGraph;
+/// let graph = LabelledGraph::<()>::from_lines("a -> b, c\nb -> c", |line| {
+///     advent_of_code::parse::arrow_list(line)
+/// })
+/// .unwrap();
+/// assert_eq!(graph.node_count(), 3);
+/// assert_eq!(graph.labels_of(&graph.toposort().unwrap()), ["a", "b", "c"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LabelledGraph<E = (), Ty: EdgeType = Directed> {
+    graph: Graph<(), E, Ty>,
+    labels: IndexSet<String>,
+}
+
+/// A [`LabelledGraph`] whose edges have no direction.
+pub type LabelledUnGraph<E = ()> = LabelledGraph<E, Undirected>;
+
+impl<E, Ty: EdgeType> Default for LabelledGraph<E, Ty> {
+    fn default() -> Self {
+        Self {
+            graph: Graph::default(),
+            labels: IndexSet::new(),
+        }
+    }
+}
+
+impl<E, Ty: EdgeType> LabelledGraph<E, Ty> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the node with the label, adding it if there is none yet.
+    pub fn add_node(&mut self, label: &str) -> NodeIndex {
+        if let Some(index) = self.labels.get_index_of(label) {
+            return NodeIndex::new(index);
+        }
+        self.labels.insert(label.to_owned());
+        self.graph.add_node(())
+    }
+
+    /// Adds an edge between the labelled nodes, adding the nodes as needed.
+    pub fn add_edge(&mut self, from: &str, to: &str, weight: E) -> EdgeIndex {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.graph.add_edge(from, to, weight)
+    }
+
+    /// Returns the node with the label.
+    #[must_use]
+    pub fn node(&self, label: &str) -> Option<NodeIndex> {
+        self.labels.get_index_of(label).map(NodeIndex::new)
+    }
+
+    /// Returns the label of the node.
+    ///
+    /// # Panics
+    /// Panics if the node is not part of the graph.
+    #[must_use]
+    pub fn label(&self, node: NodeIndex) -> &str {
+        &self.labels[node.index()]
+    }
+
+    /// Returns the labels of the nodes.
+    #[must_use]
+    pub fn labels_of(&self, nodes: &[NodeIndex]) -> Vec<&str> {
+        nodes.iter().map(|&node| self.label(node)).collect()
+    }
+
+    /// Iterates all nodes with their labels.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeIndex, &str)> {
+        self.labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| (NodeIndex::new(i), label.as_str()))
+    }
+
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    /// Returns the underlying graph, e.g. to run petgraph's algorithms on it.
+    #[must_use]
+    pub fn graph(&self) -> &Graph<(), E, Ty> {
+        &self.graph
+    }
+
+    /// Returns the connected components, see [`components`].
+    #[must_use]
+    pub fn components(&self) -> Vec<Vec<NodeIndex>> {
+        components(&self.graph)
+    }
+
+    /// Returns the nodes reachable from `start`, see [`reachable`].
+    #[must_use]
+    pub fn reachable(&self, start: NodeIndex) -> Vec<NodeIndex> {
+        reachable(&self.graph, start)
+    }
+
+    /// Renders the graph in the DOT language of Graphviz, labelling edges with `edge_label`.
+    #[must_use]
+    pub fn to_dot(&self, edge_label: impl Fn(&E) -> Option<String>) -> String {
+        let (keyword, arrow) = if Ty::is_directed() {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let mut dot = format!("{keyword} {{\n");
+        for (_, label) in self.nodes() {
+            let _ = writeln!(dot, "    {label:?};");
+        }
+        for edge in self.graph.edge_references() {
+            let (from, to) = (self.label(edge.source()), self.label(edge.target()));
+            let _ = match edge_label(edge.weight()) {
+                Some(text) => writeln!(dot, "    {from:?} {arrow} {to:?} [label={text:?}];"),
+                None => writeln!(dot, "    {from:?} {arrow} {to:?};"),
+            };
+        }
+        dot.push('}');
+        dot
+    }
+}
+
+impl<E> LabelledGraph<E, Directed> {
+    /// Returns the nodes in topological order, see [`toposort`].
+    pub fn toposort(&self) -> Result<Vec<NodeIndex>, GraphError> {
+        toposort(&self.graph).map_err(|err| match err {
+            GraphError::Cycle { node, .. } => GraphError::Cycle {
+                node,
+                label: Some(self.label(node).to_owned()),
+            },
+        })
+    }
+}
+
+impl<E: Default, Ty: EdgeType> LabelledGraph<E, Ty> {
+    /// Builds a graph from lines like `a -> b, c` or `a: b c`, which `f` splits into the
+    /// label of the source and the labels of its targets.
+    pub fn from_lines<'a>(
+        input: &'a str,
+        f: impl FnMut(&'a str) -> Result<(&'a str, Vec<&'a str>), ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut graph = Self::new();
+        for (source, targets) in parse::lines(input, f)? {
+            graph.add_node(source);
+            for target in targets {
+                graph.add_edge(source, target, E::default());
+            }
+        }
+        Ok(graph)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the connected components, ignoring the direction of edges.
+#[must_use]
+pub fn components<N, E, Ty: EdgeType>(graph: &Graph<N, E, Ty>) -> Vec<Vec<NodeIndex>> {
+    let mut sets = UnionFind::new(graph.node_count());
+    for edge in graph.edge_references() {
+        sets.union(edge.source().index(), edge.target().index());
+    }
+
+    let mut components: HashMap<usize, Vec<NodeIndex>> = HashMap::new();
+    for node in graph.node_indices() {
+        components
+            .entry(sets.find(node.index()))
+            .or_default()
+            .push(node);
+    }
+    let mut components: Vec<_> = components.into_values().collect();
+    components.sort_unstable_by_key(|component| component[0]);
+    components
+}
+
+/// Returns the nodes so that every edge points from an earlier to a later node.
+pub fn toposort<N, E>(graph: &Graph<N, E, Directed>) -> Result<Vec<NodeIndex>, GraphError> {
+    petgraph::algo::toposort(graph, None).map_err(|cycle| GraphError::Cycle {
+        node: cycle.node_id(),
+        label: None,
+    })
+}
+
+/// Returns the nodes reachable from `start` in breadth-first order, starting with `start`.
+#[must_use]
+pub fn reachable<N, E, Ty: EdgeType>(graph: &Graph<N, E, Ty>, start: NodeIndex) -> Vec<NodeIndex> {
+    let mut bfs = Bfs::new(graph, start);
+    std::iter::from_fn(|| bfs.next(graph)).collect()
+}
+
+/// A cut through a graph, splitting its nodes into two partitions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    /// The total weight of the cut edges.
+    pub weight: u64,
+    /// The edges between both partitions.
+    pub edges: Vec<EdgeIndex>,
+    pub partition: (Vec<NodeIndex>, Vec<NodeIndex>),
+}
+
+/// Finds a cut of minimal total weight with the Stoer–Wagner algorithm,
+/// or [`None`] if the graph has less than two nodes.
+pub fn min_cut<N, E>(graph: &Graph<N, E, Undirected>, weight: impl Fn(&E) -> u64) -> Option<Cut> {
+    if graph.node_count() < 2 {
+        return None;
+    }
+
+    // contract nodes on a copy, so the original indices stay valid.
+    let mut contracted = StableUnGraph::<(), u64>::with_capacity(graph.node_count(), 0);
+    for _ in graph.node_indices() {
+        contracted.add_node(());
+    }
+    for edge in graph.edge_references() {
+        contracted.add_edge(edge.source(), edge.target(), weight(edge.weight()));
+    }
+
+    let mut best: Option<(usize, u64)> = None;
+    let mut contractions = Vec::with_capacity(graph.node_count() - 1);
+    for phase in 0..graph.node_count() - 1 {
+        let ((s, t), cut_weight) = min_cut_phase(&contracted)?;
+        if best.is_none_or(|(_, weight)| cut_weight < weight) {
+            best = Some((phase, cut_weight));
+        }
+
+        // merge t into s.
+        contractions.push((s, t));
+        let edges: Vec<_> = contracted
+            .edges(t)
+            .map(|edge| (edge.target(), *edge.weight()))
+            .collect();
+        for (target, weight) in edges.into_iter().filter(|&(target, _)| target != s) {
+            match contracted.find_edge(s, target) {
+                Some(edge) => contracted[edge] += weight,
+                None => {
+                    contracted.add_edge(s, target, weight);
+                }
+            }
+        }
+        contracted.remove_node(t);
+    }
+
+    // the cut of the best phase separates t, and every node merged into it before, from the rest.
+    let (phase, weight) = best?;
+    let mut merged = StableUnGraph::<(), ()>::default();
+    for _ in graph.node_indices() {
+        merged.add_node(());
+    }
+    merged.extend_with_edges(&contractions[..phase]);
+
+    let mut side = vec![false; graph.node_count()];
+    let mut bfs = Bfs::new(&merged, contractions[phase].1);
+    while let Some(node) = bfs.next(&merged) {
+        side[node.index()] = true;
+    }
+
+    let (inside, outside) = graph.node_indices().partition(|node| side[node.index()]);
+    let edges = graph
+        .edge_references()
+        .filter(|edge| side[edge.source().index()] != side[edge.target().index()])
+        .map(|edge| edge.id())
+        .collect();
+    Some(Cut {
+        weight,
+        edges,
+        partition: (inside, outside),
+    })
+}
+
+/// Adds nodes in order of how tightly they are connected to the ones added before,
+/// returns the last two nodes and the weight between the last one and all others.
+fn min_cut_phase(graph: &StableUnGraph<(), u64>) -> Option<((NodeIndex, NodeIndex), u64)> {
+    let mut queue: PriorityQueue<NodeIndex, u64, ahash::RandomState> =
+        graph.node_identifiers().map(|node| (node, 0)).collect();
+
+    let (mut s, mut t, mut cut_weight) = (None, None, 0);
+    while let Some((node, weight)) = queue.pop() {
+        (s, t, cut_weight) = (t, Some(node), weight);
+        for edge in graph.edges(node) {
+            queue.change_priority_by(&edge.target(), |w| *w += *edge.weight());
+        }
+    }
+
+    Some(((s?, t?), cut_weight))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned by the graph algorithms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// The graph contains a cycle through the node.
+    Cycle {
+        node: NodeIndex,
+        label: Option<String>,
+    },
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::Cycle {
+                label: Some(label), ..
+            } => write!(f, "the graph has a cycle through {label:?}."),
+            GraphError::Cycle { node, .. } => {
+                write!(f, "the graph has a cycle through node {}.", node.index())
+            }
+        }
+    }
+}
+
+impl Error for GraphError {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{components, min_cut, GraphError, LabelledGraph, LabelledUnGraph};
+    use crate::parse;
+
+    #[test]
+    fn interns_labels() {
+        let mut graph = LabelledGraph::<u32>::new();
+        let a = graph.add_node("a");
+        graph.add_edge("a", "b", 1);
+        graph.add_edge("b", "a", 2);
+        assert_eq!(graph.add_node("a"), a);
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.label(graph.node("b").unwrap()), "b");
+        assert_eq!(graph.node("c"), None);
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let graph = LabelledGraph::<()>::from_lines("c -> d\na -> b, c\nb -> d", parse::arrow_list)
+            .unwrap();
+        let order = graph.labels_of(&graph.toposort().unwrap());
+        let position = |label| order.iter().position(|&l| l == label).unwrap();
+        assert!(position("a") < position("b"));
+        assert!(position("a") < position("c"));
+        assert!(position("c") < position("d") && position("b") < position("d"));
+
+        let cyclic = LabelledGraph::<()>::from_lines("a -> b\nb -> a", parse::arrow_list).unwrap();
+        assert!(matches!(
+            cyclic.toposort(),
+            Err(GraphError::Cycle { label: Some(_), .. })
+        ));
+    }
+
+    #[test]
+    fn finds_components_and_reachable_nodes() {
+        let graph =
+            LabelledGraph::<()>::from_lines("a -> b\nc -> d, e\nf -> ", parse::arrow_list).unwrap();
+        let components: Vec<_> = graph
+            .components()
+            .iter()
+            .map(|component| graph.labels_of(component))
+            .collect();
+        assert_eq!(components, [vec!["a", "b"], vec!["c", "d", "e"], vec!["f"]]);
+
+        let c = graph.node("c").unwrap();
+        let mut reachable = graph.labels_of(&graph.reachable(c));
+        assert_eq!(reachable[0], "c");
+        reachable.sort_unstable();
+        assert_eq!(reachable, ["c", "d", "e"]);
+        let d = graph.node("d").unwrap();
+        assert_eq!(graph.labels_of(&graph.reachable(d)), ["d"]);
+    }
+
+    #[test]
+    fn cuts_graphs() {
+        // two triangles, joined by a single edge.
+        let input = "a: b c\nb: c\nc: d\nd: e f\ne: f";
+        let graph = LabelledUnGraph::<()>::from_lines(input, |line| {
+            let (source, targets) = parse::key_value(line, ":")?;
+            Ok((source, targets.split_whitespace().collect()))
+        })
+        .unwrap();
+
+        let cut = min_cut(graph.graph(), |_| 1).unwrap();
+        assert_eq!(cut.weight, 1);
+        assert_eq!(cut.edges.len(), 1);
+        let (mut left, mut right) = (
+            graph.labels_of(&cut.partition.0),
+            graph.labels_of(&cut.partition.1),
+        );
+        left.sort_unstable();
+        right.sort_unstable();
+        let mut sides = [left, right];
+        sides.sort();
+        assert_eq!(sides, [vec!["a", "b", "c"], vec!["d", "e", "f"]]);
+        assert_eq!(components(graph.graph()).len(), 1);
+    }
+
+    #[test]
+    fn cuts_weighted_graphs() {
+        let mut graph = LabelledUnGraph::<u64>::new();
+        graph.add_edge("a", "b", 5);
+        graph.add_edge("b", "c", 2);
+        graph.add_edge("c", "d", 5);
+        graph.add_edge("d", "a", 3);
+        let cut = min_cut(graph.graph(), |&w| w).unwrap();
+        assert_eq!(cut.weight, 5);
+        assert_eq!(cut.edges.len(), 2);
+        assert!(min_cut(LabelledUnGraph::<()>::new().graph(), |_| 1).is_none());
+    }
+
+    #[test]
+    fn renders_dot() {
+        let mut graph = LabelledGraph::<u32>::new();
+        graph.add_edge("a", "b", 3);
+        assert_eq!(
+            graph.to_dot(|w| Some(w.to_string())),
+            "digraph {\n    \"a\";\n    \"b\";\n    \"a\" -> \"b\" [label=\"3\"];\n}"
+        );
+
+        let mut graph = LabelledUnGraph::<()>::new();
+        graph.add_edge("a", "b", ());
+        assert_eq!(
+            graph.to_dot(|_| None),
+            "graph {\n    \"a\";\n    \"b\";\n    \"a\" -- \"b\";\n}"
+        );
+    }
+}
@@ -0,0 +1,614 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::iter::StepBy;
+use std::ops::{Index, IndexMut};
+use std::slice;
+use std::str::FromStr;
+
+/// Offsets of the four orthogonal neighbours, in the order up, right, down, left.
+pub const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbours, clockwise starting at the top left.
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular grid of cells, stored row by row in a single [`Vec`].
+///
+/// Positions are `(x, y)` tuples, with `x` being the column and `y` the row,
+/// starting at the top left.
+///
+/// ```
+/// # use advent_of_code::grid::Grid;
+/// let grid: Grid<char> = "#.\n.#".parse().unwrap();
+/// assert_eq!(grid[(1, 0)], '.');
+/// assert_eq!(grid.find(&'#'), Some((0, 0)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a [`Grid`] from cells in row-major order,
+    /// returns [`None`] if the number of cells does not match the size.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        if width.checked_mul(height)? != cells.len() {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Creates a [`Grid`] by calling `f` with the position of every cell.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a [`Grid`] with one line per row, mapping each character to a cell.
+    /// All rows must have the same width and `f` must accept every character.
+    pub fn parse(s: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, GridParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (y, line) in s.lines().enumerate() {
+            let mut row_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).ok_or(GridParseError::InvalidCell { x, y, cell: c })?);
+                row_width += 1;
+            }
+
+            match width {
+                None => width = Some(row_width),
+                Some(expected) if expected != row_width => {
+                    return Err(GridParseError::RaggedRow {
+                        y,
+                        expected,
+                        found: row_width,
+                    })
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            None | Some(0) => Err(GridParseError::Empty),
+            Some(width) => Ok(Self {
+                width,
+                height,
+                cells,
+            }),
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the number of cells.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns true if the signed position lies inside the grid.
+    #[must_use]
+    pub fn contains(&self, (x, y): (isize, isize)) -> bool {
+        self.to_unsigned((x, y)).is_some()
+    }
+
+    fn to_unsigned(&self, (x, y): (isize, isize)) -> Option<(usize, usize)> {
+        let x = usize::try_from(x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(y).ok().filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    fn index_of(&self, (x, y): (usize, usize)) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Returns the cell at a position, or [`None`] if it lies outside the grid.
+    #[must_use]
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Like [`Grid::get`], for signed positions that may lie left of or above the grid.
+    #[must_use]
+    pub fn get_signed(&self, pos: (isize, isize)) -> Option<&T> {
+        self.get(self.to_unsigned(pos)?)
+    }
+
+    /// Returns the cell at a position of the grid repeated infinitely in every direction.
+    ///
+    /// # Panics
+    /// Panics if the grid is empty.
+    #[must_use]
+    pub fn get_wrapping(&self, pos: (isize, isize)) -> &T {
+        &self[self.wrap(pos)]
+    }
+
+    /// Maps a signed position onto the grid, as if it was repeated infinitely in every direction.
+    ///
+    /// # Panics
+    /// Panics if the grid is empty.
+    #[must_use]
+    pub fn wrap(&self, (x, y): (isize, isize)) -> (usize, usize) {
+        // grid sizes fit into isize, as the cells are allocated.
+        #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        (
+            x.rem_euclid(self.width as isize) as usize,
+            y.rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    /// Returns the position moved by an offset, if it stays inside the grid.
+    #[must_use]
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// Iterates the orthogonal neighbours of a position that lie inside the grid.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// Iterates the orthogonal and diagonal neighbours of a position that lie inside the grid.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// Returns a row as a slice.
+    ///
+    /// # Panics
+    /// Panics if the row lies outside the grid.
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// # Panics
+    /// Panics if the row lies outside the grid.
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates the cells of a column, top to bottom.
+    ///
+    /// # Panics
+    /// Panics if the column lies outside the grid.
+    pub fn col(&self, x: usize) -> StepBy<slice::Iter<'_, T>> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = StepBy<slice::Iter<'_, T>>> {
+        (0..self.width).map(|x| self.col(x))
+    }
+
+    /// Iterates all cells in row-major order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Iterates all positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Iterates all cells together with their position, in row-major order.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns the position of the first cell (in row-major order) matching a predicate.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let i = self.cells.iter().position(&mut predicate)?;
+        Some((i % self.width, i / self.width))
+    }
+
+    /// Creates a new grid of the same size by mapping every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Consumes the grid, returning the cells in row-major order.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<T> {
+        self.cells
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Returns the position of the first cell (in row-major order) equal to `value`.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)> {
+        self.position(|cell| cell == value)
+    }
+
+    /// Iterates the positions of all cells equal to `value`.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.enumerate()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a [`Grid`] with every cell set to `value`.
+    #[must_use]
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Returns the grid mirrored along its main diagonal, i.e. rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// Returns the grid rotated by 90° clockwise.
+    #[must_use]
+    pub fn rotate_cw(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Returns the grid rotated by 90° counterclockwise.
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(x, y)| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    /// Returns the grid rotated by 180°.
+    #[must_use]
+    pub fn rotate_180(&self) -> Self {
+        let mut cells = self.cells.clone();
+        cells.reverse();
+        Self { cells, ..*self }
+    }
+
+    /// Returns the grid mirrored left to right.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        let mut flipped = self.clone();
+        for y in 0..self.height {
+            flipped.row_mut(y).reverse();
+        }
+        flipped
+    }
+
+    /// Returns the grid mirrored top to bottom.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.width, self.height, |(x, y)| {
+            self[(x, self.height - 1 - y)].clone()
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        let i = self
+            .index_of((x, y))
+            .unwrap_or_else(|| panic!("position ({x}, {y}) is out of bounds"));
+        &self.cells[i]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        let i = self
+            .index_of((x, y))
+            .unwrap_or_else(|| panic!("position ({x}, {y}) is out of bounds"));
+        &mut self.cells[i]
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Displays one line per row, without a trailing newline.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Grid<char> {
+    type Err = GridParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Some)
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridParseError {
+    Empty,
+    RaggedRow {
+        y: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        x: usize,
+        y: usize,
+        cell: char,
+    },
+}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "the grid is empty."),
+            GridParseError::RaggedRow { y, expected, found } => write!(
+                f,
+                "row {y} is {found} cells wide, expected {expected} like the first row."
+            ),
+            GridParseError::InvalidCell { x, y, cell } => {
+                write!(f, "invalid cell {cell:?} at ({x}, {y}).")
+            }
+        }
+    }
+}
+
+impl Error for GridParseError {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridParseError};
+
+    fn sample() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_chars() {
+        let grid = sample();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid[(0, 0)], 'a');
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!("abc\ndef\n".parse::<Grid<char>>().unwrap(), grid);
+    }
+
+    #[test]
+    fn parses_with_mapping() {
+        let grid = Grid::parse("19\n28", |c| c.to_digit(10)).unwrap();
+        assert_eq!(grid.into_inner(), vec![1, 9, 2, 8]);
+
+        assert_eq!(
+            Grid::parse("12\n3x", |c| c.to_digit(10)),
+            Err(GridParseError::InvalidCell {
+                x: 1,
+                y: 1,
+                cell: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!("".parse::<Grid<char>>(), Err(GridParseError::Empty));
+        assert_eq!("\n\n".parse::<Grid<char>>(), Err(GridParseError::Empty));
+        assert_eq!(
+            "ab\nabc".parse::<Grid<char>>(),
+            Err(GridParseError::RaggedRow {
+                y: 1,
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn creates_grids() {
+        assert!(Grid::new(2, 2, vec![0; 3]).is_none());
+        assert_eq!(Grid::new(2, 1, vec![0, 0]), Some(Grid::filled(2, 1, 0)));
+
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(grid.into_inner(), vec![0, 1, 2, 10, 11, 12]);
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let mut grid = sample();
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.get_signed((-1, 0)), None);
+        assert_eq!(grid.get_signed((1, 1)), Some(&'e'));
+        assert!(grid.contains((0, 0)));
+        assert!(!grid.contains((0, -1)));
+
+        *grid.get_mut((1, 0)).unwrap() = 'x';
+        grid[(2, 0)] = 'y';
+        assert_eq!(grid.row(0), ['a', 'x', 'y']);
+        assert!(grid.get_mut((5, 5)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn panics_when_indexing_outside() {
+        let _ = sample()[(3, 0)];
+    }
+
+    #[test]
+    fn wraps_positions() {
+        let grid = sample();
+        assert_eq!(grid.wrap((3, 2)), (0, 0));
+        assert_eq!(grid.wrap((-1, -1)), (2, 1));
+        assert_eq!(*grid.get_wrapping((-4, 5)), 'f');
+        assert_eq!(*grid.get_wrapping((1, 0)), 'b');
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let grid = Grid::filled(3, 3, 0);
+        assert_eq!(
+            grid.neighbors4((1, 1)).collect::<Vec<_>>(),
+            [(1, 0), (2, 1), (1, 2), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbors8((2, 2)).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (2, 1)), Some((2, 1)));
+    }
+
+    #[test]
+    fn views_rows_and_cols() {
+        let grid = sample();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.col(1).copied().collect::<String>(), "be");
+        assert_eq!(grid.col(2).rev().copied().collect::<String>(), "fc");
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(
+            grid.cols()
+                .map(|col| col.copied().collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn iterates_cells() {
+        let grid = sample();
+        assert_eq!(grid.iter().collect::<String>(), "abcdef");
+        assert_eq!(grid.positions().nth(4), Some((1, 1)));
+        assert_eq!(grid.enumerate().nth(2), Some(((2, 0), &'c')));
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid: Grid<char> = "#.#\n..#".parse().unwrap();
+        assert_eq!(grid.find(&'#'), Some((0, 0)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            [(0, 0), (2, 0), (2, 1)]
+        );
+        assert_eq!(grid.position(|&c| c == '.'), Some((1, 0)));
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = sample();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_180().to_string(), "fed\ncba");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.rotate_cw().rotate_cw(), grid.rotate_180());
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(grid.transpose().width(), 2);
+    }
+
+    #[test]
+    fn displays_rows() {
+        let grid = Grid::from_fn(2, 2, |(x, y)| x + y);
+        assert_eq!(grid.to_string(), "01\n12");
+    }
+}
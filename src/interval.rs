@@ -0,0 +1,518 @@
+use std::fmt::{self, Display};
+use std::ops::{Range, RangeInclusive};
+
+use num::PrimInt;
+
+/// A half-open interval `start..end` of integers. Intervals with `end <= start` are empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    /// Creates the half-open interval `start..end`.
+    #[must_use]
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates the closed interval `first..=last`.
+    ///
+    /// # Panics
+    /// Panics if `last` is the largest value of `T`, as the end would not be representable.
+    #[must_use]
+    pub fn closed(first: T, last: T) -> Self {
+        let end = last
+            .checked_add(&T::one())
+            .expect("end of interval overflows");
+        Self { start: first, end }
+    }
+
+    /// Creates the interval `start..start + len`.
+    #[must_use]
+    pub fn with_len(start: T, len: T) -> Self {
+        Self {
+            start,
+            end: start + len,
+        }
+    }
+
+    /// Returns the last value in the interval, or [`None`] if it is empty.
+    #[must_use]
+    pub fn last(&self) -> Option<T> {
+        (!self.is_empty()).then(|| self.end - T::one())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Returns the number of values in the interval.
+    #[must_use]
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Returns whether both intervals have a value in common.
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Returns the values in both intervals, which may be empty.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        }
+    }
+
+    /// Splits the interval into the values below `at` and the values from `at` on.
+    /// Either half may be empty.
+    #[must_use]
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let at = at.clamp(self.start, self.end.max(self.start));
+        (Self::new(self.start, at), Self::new(at, self.end))
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl<T: PrimInt> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (first, last) = range.into_inner();
+        Self::closed(first, last)
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..interval.end
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// A set of integers, stored as sorted intervals that neither overlap nor touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the normalized intervals in ascending order.
+    #[must_use]
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the number of values in the set.
+    #[must_use]
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |len, interval| len + interval.len())
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    #[must_use]
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().and_then(Interval::last)
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        // the first interval that ends after the value is the only one that can contain it.
+        let index = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Adds all values of the interval to the set.
+    pub fn insert(&mut self, interval: impl Into<Interval<T>>) {
+        let mut interval = interval.into();
+        if interval.is_empty() {
+            return;
+        }
+
+        // merge with every interval that overlaps or touches the new one.
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        if first < last {
+            interval.start = interval.start.min(self.intervals[first].start);
+            interval.end = interval.end.max(self.intervals[last - 1].end);
+        }
+        self.intervals.splice(first..last, [interval]);
+    }
+
+    /// Removes all values of the interval from the set.
+    pub fn remove(&mut self, interval: impl Into<Interval<T>>) {
+        let interval = interval.into();
+        if interval.is_empty() {
+            return;
+        }
+
+        let first = self.intervals.partition_point(|i| i.end <= interval.start);
+        let last = self.intervals.partition_point(|i| i.start < interval.end);
+        if first >= last {
+            return;
+        }
+        let before = Interval::new(self.intervals[first].start, interval.start);
+        let after = Interval::new(interval.end, self.intervals[last - 1].end);
+        let kept = [before, after].into_iter().filter(|i| !i.is_empty());
+        self.intervals.splice(first..last, kept);
+    }
+
+    /// Returns the values in either set.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.intervals.iter().copied());
+        union
+    }
+
+    /// Returns the values in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (
+            self.intervals.iter().peekable(),
+            other.intervals.iter().peekable(),
+        );
+        let mut intervals = vec![];
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let common = x.intersection(y);
+            if !common.is_empty() {
+                intervals.push(common);
+            }
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { intervals }
+    }
+
+    /// Returns the values in this set but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for &interval in &other.intervals {
+            difference.remove(interval);
+        }
+        difference
+    }
+
+    /// Splits the set into the values below `at` and the values from `at` on.
+    #[must_use]
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let (mut below, mut above) = (Self::new(), Self::new());
+        for interval in &self.intervals {
+            let (low, high) = interval.split_at(at);
+            below.insert(low);
+            above.insert(high);
+        }
+        (below, above)
+    }
+}
+
+impl<T: PrimInt, I: Into<Interval<T>>> FromIterator<I> for IntervalSet<T> {
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: PrimInt, I: Into<Interval<T>>> Extend<I> for IntervalSet<T> {
+    fn extend<It: IntoIterator<Item = I>>(&mut self, iter: It) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T: PrimInt> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::from_iter([interval])
+    }
+}
+
+/// A piecewise-linear map that shifts each of its source intervals onto a destination,
+/// and maps every other value onto itself.
+///
+/// The map is defined on `T::min_value()..T::max_value()`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalMap<T> {
+    /// Disjoint source intervals in ascending order, with the start of their destination.
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T: PrimInt> IntervalMap<T> {
+    /// Creates the identity map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { pieces: vec![] }
+    }
+
+    /// Maps the values of `source` onto `destination..destination + source.len()`.
+    ///
+    /// # Panics
+    /// Panics if `source` overlaps a source that is already mapped.
+    pub fn insert(&mut self, source: impl Into<Interval<T>>, destination: T) {
+        let source = source.into();
+        if source.is_empty() {
+            return;
+        }
+        let index = self.pieces.partition_point(|(s, _)| s.start < source.start);
+        let overlaps = |i: usize| self.pieces.get(i).is_some_and(|(s, _)| s.overlaps(&source));
+        assert!(
+            !overlaps(index) && (index == 0 || !overlaps(index - 1)),
+            "source overlaps an existing piece"
+        );
+        self.pieces.insert(index, (source, destination));
+    }
+
+    /// Returns the source intervals with the start of their destination, in ascending order.
+    #[must_use]
+    pub fn pieces(&self) -> &[(Interval<T>, T)] {
+        &self.pieces
+    }
+
+    #[must_use]
+    pub fn map(&self, value: T) -> T {
+        let index = self.pieces.partition_point(|(s, _)| s.end <= value);
+        match self.pieces.get(index) {
+            Some(&(source, destination)) if source.contains(value) => {
+                shift(value, source.start, destination)
+            }
+            _ => value,
+        }
+    }
+
+    /// Maps every interval of the set at once.
+    #[must_use]
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut mapped = IntervalSet::new();
+        for interval in set.intervals() {
+            for (source, destination) in self.segments() {
+                let part = source.intersection(interval);
+                if !part.is_empty() {
+                    mapped.insert(Interval::new(
+                        shift(part.start, source.start, destination),
+                        shift(part.end, source.start, destination),
+                    ));
+                }
+            }
+        }
+        mapped
+    }
+
+    /// Returns the map that applies this map and then `next`.
+    #[must_use]
+    pub fn then(&self, next: &Self) -> Self {
+        let mut composed = Self::new();
+        for (source, destination) in self.segments() {
+            let image = Interval::new(destination, shift(source.end, source.start, destination));
+            for (next_source, next_destination) in next.segments() {
+                let part = image.intersection(&next_source);
+                if part.is_empty() {
+                    continue;
+                }
+                let start = shift(part.start, destination, source.start);
+                let end = shift(part.end, destination, source.start);
+                let target = shift(part.start, next_source.start, next_destination);
+                if target != start {
+                    composed.pieces.push((Interval::new(start, end), target));
+                }
+            }
+        }
+        composed
+    }
+
+    /// Iterates disjoint intervals that cover the whole domain, with the start of their destination.
+    fn segments(&self) -> impl Iterator<Item = (Interval<T>, T)> + '_ {
+        let mut position = T::min_value();
+        self.pieces
+            .iter()
+            .copied()
+            .chain([(
+                Interval::new(T::max_value(), T::max_value()),
+                T::max_value(),
+            )])
+            .flat_map(move |(source, destination)| {
+                let gap = Interval::new(position, source.start);
+                position = source.end;
+                [(gap, gap.start), (source, destination)]
+            })
+            .filter(|(interval, _)| !interval.is_empty())
+    }
+}
+
+/// Returns `value - from + to` without overflowing in between.
+fn shift<T: PrimInt>(value: T, from: T, to: T) -> T {
+    if to >= from {
+        value + (to - from)
+    } else {
+        value - (from - to)
+    }
+}
+
+impl<T: PrimInt, I: Into<Interval<T>>> FromIterator<(I, T)> for IntervalMap<T> {
+    fn from_iter<It: IntoIterator<Item = (I, T)>>(iter: It) -> Self {
+        let mut map = Self::new();
+        for (source, destination) in iter {
+            map.insert(source, destination);
+        }
+        map
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Interval, IntervalMap, IntervalSet};
+
+    #[test]
+    fn converts_closed_intervals() {
+        let interval = Interval::from(3..=5);
+        assert_eq!(interval, Interval::new(3, 6));
+        assert_eq!(interval.len(), 3);
+        assert_eq!(interval.last(), Some(5));
+        assert!(interval.contains(5));
+        assert!(!interval.contains(6));
+        assert_eq!(Interval::new(4, 2).len(), 0);
+        assert_eq!(Interval::new(4, 4).last(), None);
+    }
+
+    #[test]
+    fn splits_intervals() {
+        let interval = Interval::new(2, 8);
+        assert_eq!(
+            interval.split_at(5),
+            (Interval::new(2, 5), Interval::new(5, 8))
+        );
+        assert_eq!(interval.split_at(0), (Interval::new(2, 2), interval));
+        assert_eq!(interval.split_at(10), (interval, Interval::new(8, 8)));
+        assert_eq!(
+            interval.intersection(&Interval::new(6, 10)),
+            Interval::new(6, 8)
+        );
+        assert!(!interval.overlaps(&Interval::new(8, 10)));
+    }
+
+    #[test]
+    fn normalizes_sets() {
+        let set: IntervalSet<i32> = [0..3, 10..12, 3..5, 7..8, 11..15, 20..20]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            set.intervals(),
+            [
+                Interval::new(0, 5),
+                Interval::new(7, 8),
+                Interval::new(10, 15)
+            ]
+        );
+        assert_eq!(set.len(), 11);
+        assert_eq!((set.min(), set.max()), (Some(0), Some(14)));
+        assert!(set.contains(4) && set.contains(7) && !set.contains(5) && !set.contains(15));
+
+        let mut merged = set.clone();
+        merged.insert(4..11);
+        assert_eq!(merged.intervals(), [Interval::new(0, 15)]);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a: IntervalSet<i32> = [0..10, 20..30].into_iter().collect();
+        let b = IntervalSet::from(Interval::new(5, 25));
+
+        let union = IntervalSet::from(Interval::new(0, 30));
+        let intersection: IntervalSet<i32> = [5..10, 20..25].into_iter().collect();
+        let difference: IntervalSet<i32> = [0..5, 25..30].into_iter().collect();
+        assert_eq!(a.union(&b), union);
+        assert_eq!(a.intersection(&b), intersection);
+        assert_eq!(a.difference(&b), difference);
+        assert_eq!(b.difference(&a), Interval::new(10, 20).into());
+
+        let (below, above) = a.split_at(25);
+        assert_eq!(below, [0..10, 20..25].into_iter().collect());
+        assert_eq!(above, Interval::new(25, 30).into());
+    }
+
+    #[test]
+    fn maps_values_and_sets() {
+        // the seed-to-soil map of 2023 day 5.
+        let map: IntervalMap<u64> = [(98..100, 50), (50..98, 52)].into_iter().collect();
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(98), 50);
+        assert_eq!(map.map(10), 10);
+
+        let seeds: IntervalSet<u64> = [79..93, 95..101].into_iter().collect();
+        let soil: IntervalSet<u64> = [50..52, 81..95, 97..100, 100..101].into_iter().collect();
+        assert_eq!(map.map_set(&seeds), soil);
+    }
+
+    #[test]
+    fn composes_maps() {
+        let first: IntervalMap<i64> = [(0..10, 100), (20..30, 5)].into_iter().collect();
+        let second: IntervalMap<i64> = [(0..8, 50), (100..105, 0)].into_iter().collect();
+        let composed = first.then(&second);
+        for value in -5..40 {
+            assert_eq!(composed.map(value), second.map(first.map(value)), "{value}");
+        }
+
+        let values = IntervalSet::from(Interval::new(-5, 40));
+        assert_eq!(
+            composed.map_set(&values),
+            second.map_set(&first.map_set(&values))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps")]
+    fn rejects_overlapping_pieces() {
+        let _: IntervalMap<u32> = [(0..10, 20), (5..15, 40)].into_iter().collect();
+    }
+}
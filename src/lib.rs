@@ -1,4 +1,24 @@
+pub mod bitgrid;
+pub mod cycle;
 mod day;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod linalg;
+pub mod math;
+pub mod maze;
+pub mod memo;
+pub mod parse;
+mod part;
+pub mod prelude;
+mod puzzle_id;
+pub mod search;
 pub mod template;
+pub mod viz;
+mod year;
 
 pub use day::*;
+pub use part::*;
+pub use puzzle_id::*;
+pub use year::*;
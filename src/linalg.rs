@@ -0,0 +1,353 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use num::{BigInt, BigRational, One, Zero};
+
+/// A dense matrix of exact rationals, stored row by row.
+///
+/// Everything is computed without rounding, so results can be checked for being integers
+/// and singular systems are detected reliably.
+///
+/// ```
+/// # use advent_of_code::linalg::{rational, Matrix};
+/// // x + y = 3, x - y = 1
+/// let a = Matrix::from_rows([[1, 1], [1, -1]]);
+/// assert_eq!(a.solve(&[rational(3), rational(1)]), Ok(vec![rational(2), rational(1)]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    cells: Vec<BigRational>,
+}
+
+/// Converts an integer to an exact rational.
+#[must_use]
+pub fn rational(value: impl Into<BigInt>) -> BigRational {
+    BigRational::from_integer(value.into())
+}
+
+impl Matrix {
+    /// Creates a matrix filled with zeros.
+    #[must_use]
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            cells: vec![BigRational::zero(); rows * cols],
+        }
+    }
+
+    /// Creates the `n` by `n` identity matrix.
+    #[must_use]
+    pub fn identity(n: usize) -> Self {
+        let mut matrix = Self::zeros(n, n);
+        for i in 0..n {
+            matrix[(i, i)] = BigRational::one();
+        }
+        matrix
+    }
+
+    /// Creates a matrix from rows of integers.
+    ///
+    /// # Panics
+    /// Panics if the rows do not have the same length.
+    #[must_use]
+    pub fn from_rows<R, T>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = T>,
+        T: Into<BigInt>,
+    {
+        let mut matrix = Self::zeros(0, 0);
+        for row in rows {
+            let row: Vec<_> = row.into_iter().map(rational).collect();
+            if matrix.rows == 0 {
+                matrix.cols = row.len();
+            }
+            assert_eq!(row.len(), matrix.cols, "rows must have the same length");
+            matrix.cells.extend(row);
+            matrix.rows += 1;
+        }
+        matrix
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the cells of row `r`.
+    #[must_use]
+    pub fn row(&self, r: usize) -> &[BigRational] {
+        &self.cells[r * self.cols..(r + 1) * self.cols]
+    }
+
+    /// Appends a row.
+    ///
+    /// # Panics
+    /// Panics if the row does not have one cell per column.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = BigRational>) {
+        let len = self.cells.len();
+        self.cells.extend(row);
+        assert_eq!(
+            self.cells.len() - len,
+            self.cols,
+            "row has the wrong length"
+        );
+        self.rows += 1;
+    }
+
+    /// Returns the product with the column vector `x`.
+    ///
+    /// # Panics
+    /// Panics if `x` does not have one entry per column.
+    #[must_use]
+    pub fn mul_vec(&self, x: &[BigRational]) -> Vec<BigRational> {
+        assert_eq!(x.len(), self.cols, "vector has the wrong length");
+        (0..self.rows)
+            .map(|r| self.row(r).iter().zip(x).map(|(a, b)| a * b).sum())
+            .collect()
+    }
+
+    /// Brings the matrix into reduced row echelon form in place and returns the pivot columns.
+    pub fn row_reduce(&mut self) -> Vec<usize> {
+        let mut pivots = vec![];
+        for col in 0..self.cols {
+            let r = pivots.len();
+            let Some(pivot) = (r..self.rows).find(|&i| !self[(i, col)].is_zero()) else {
+                continue;
+            };
+            self.swap_rows(r, pivot);
+
+            let factor = self[(r, col)].recip();
+            for c in col..self.cols {
+                self[(r, c)] *= &factor;
+            }
+            for i in (0..self.rows).filter(|&i| i != r) {
+                let factor = self[(i, col)].clone();
+                if factor.is_zero() {
+                    continue;
+                }
+                for c in col..self.cols {
+                    let delta = &factor * &self[(r, c)];
+                    self[(i, c)] -= delta;
+                }
+            }
+
+            pivots.push(col);
+            if pivots.len() == self.rows {
+                break;
+            }
+        }
+        pivots
+    }
+
+    /// Returns the number of linearly independent rows.
+    #[must_use]
+    pub fn rank(&self) -> usize {
+        self.clone().row_reduce().len()
+    }
+
+    /// Returns the determinant, or [`None`] if the matrix is not square.
+    #[must_use]
+    pub fn determinant(&self) -> Option<BigRational> {
+        if self.rows != self.cols {
+            return None;
+        }
+
+        // eliminate below the diagonal, tracking the sign of every row swap.
+        let mut m = self.clone();
+        let mut det = BigRational::one();
+        for col in 0..m.cols {
+            let Some(pivot) = (col..m.rows).find(|&i| !m[(i, col)].is_zero()) else {
+                return Some(BigRational::zero());
+            };
+            if pivot != col {
+                m.swap_rows(col, pivot);
+                det = -det;
+            }
+            det *= &m[(col, col)];
+            for i in col + 1..m.rows {
+                let factor = &m[(i, col)] / &m[(col, col)];
+                for c in col..m.cols {
+                    let delta = &factor * &m[(col, c)];
+                    m[(i, c)] -= delta;
+                }
+            }
+        }
+        Some(det)
+    }
+
+    /// Solves `self * x = b` with Gaussian elimination.
+    ///
+    /// There may be more equations than unknowns, as long as they are consistent.
+    pub fn solve(&self, b: &[BigRational]) -> Result<Vec<BigRational>, LinalgError> {
+        if b.len() != self.rows {
+            return Err(LinalgError::DimensionMismatch {
+                rows: self.rows,
+                found: b.len(),
+            });
+        }
+
+        let mut augmented = Self::zeros(0, self.cols + 1);
+        for (r, rhs) in b.iter().enumerate() {
+            augmented.push_row(self.row(r).iter().chain([rhs]).cloned());
+        }
+
+        let pivots = augmented.row_reduce();
+        if pivots.last() == Some(&self.cols) {
+            return Err(LinalgError::Inconsistent);
+        }
+        if pivots.len() < self.cols {
+            return Err(LinalgError::Underdetermined {
+                rank: pivots.len(),
+                unknowns: self.cols,
+            });
+        }
+
+        Ok((0..self.cols)
+            .map(|r| augmented[(r, self.cols)].clone())
+            .collect())
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            for c in 0..self.cols {
+                self.cells.swap(a * self.cols + c, b * self.cols + c);
+            }
+        }
+    }
+}
+
+/// Cells are indexed by `(row, column)`.
+impl Index<(usize, usize)> for Matrix {
+    type Output = BigRational;
+
+    fn index(&self, (r, c): (usize, usize)) -> &Self::Output {
+        assert!(
+            r < self.rows && c < self.cols,
+            "({r}, {c}) is out of bounds"
+        );
+        &self.cells[r * self.cols + c]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            r < self.rows && c < self.cols,
+            "({r}, {c}) is out of bounds"
+        );
+        &mut self.cells[r * self.cols + c]
+    }
+}
+
+/// An error which can be returned when solving a linear system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinalgError {
+    /// The right-hand side does not have one entry per row.
+    DimensionMismatch { rows: usize, found: usize },
+    /// The equations contradict each other, so there is no solution.
+    Inconsistent,
+    /// The equations do not determine all unknowns, so there are infinitely many solutions.
+    Underdetermined { rank: usize, unknowns: usize },
+}
+
+impl Display for LinalgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinalgError::DimensionMismatch { rows, found } => {
+                write!(
+                    f,
+                    "expected {rows} values on the right-hand side, found {found}."
+                )
+            }
+            LinalgError::Inconsistent => write!(f, "the system has no solution."),
+            LinalgError::Underdetermined { rank, unknowns } => write!(
+                f,
+                "the system has rank {rank}, which does not determine all {unknowns} unknowns."
+            ),
+        }
+    }
+}
+
+impl Error for LinalgError {}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use num::BigRational;
+
+    use super::{rational, LinalgError, Matrix};
+
+    #[test]
+    fn solves_square_systems() {
+        // 2x + y - z = 8, -3x - y + 2z = -11, -2x + y + 2z = -3
+        let a = Matrix::from_rows([[2, 1, -1], [-3, -1, 2], [-2, 1, 2]]);
+        let b = [rational(8), rational(-11), rational(-3)];
+        let x = a.solve(&b).unwrap();
+        assert_eq!(x, [rational(2), rational(3), rational(-1)]);
+        assert_eq!(a.mul_vec(&x), b);
+    }
+
+    #[test]
+    fn solves_with_fractions() {
+        let a = Matrix::from_rows([[3, 0], [0, 4]]);
+        let x = a.solve(&[rational(1), rational(2)]).unwrap();
+        assert_eq!(
+            x,
+            [
+                BigRational::new(1.into(), 3.into()),
+                BigRational::new(1.into(), 2.into())
+            ]
+        );
+    }
+
+    #[test]
+    fn solves_consistent_overdetermined_systems() {
+        let a = Matrix::from_rows([[1, 1], [1, -1], [2, 0]]);
+        let b = [rational(3), rational(1), rational(4)];
+        assert_eq!(a.solve(&b), Ok(vec![rational(2), rational(1)]));
+
+        let b = [rational(3), rational(1), rational(5)];
+        assert_eq!(a.solve(&b), Err(LinalgError::Inconsistent));
+    }
+
+    #[test]
+    fn rejects_singular_systems() {
+        let a = Matrix::from_rows([[1, 2], [2, 4]]);
+        assert_eq!(
+            a.solve(&[rational(1), rational(2)]),
+            Err(LinalgError::Underdetermined {
+                rank: 1,
+                unknowns: 2
+            })
+        );
+        assert_eq!(
+            a.solve(&[rational(1)]),
+            Err(LinalgError::DimensionMismatch { rows: 2, found: 1 })
+        );
+        assert_eq!(a.rank(), 1);
+        assert_eq!(a.determinant(), Some(rational(0)));
+    }
+
+    #[test]
+    fn computes_determinants() {
+        assert_eq!(Matrix::identity(4).determinant(), Some(rational(1)));
+        let a = Matrix::from_rows([[0, 2, 1], [1, 0, 0], [3, 1, 2]]);
+        assert_eq!(a.determinant(), Some(rational(-3)));
+        assert_eq!(Matrix::zeros(2, 3).determinant(), None);
+    }
+
+    #[test]
+    fn reduces_rows() {
+        let mut a = Matrix::from_rows([[1, 2, 3], [2, 4, 7]]);
+        assert_eq!(a.row_reduce(), [0, 2]);
+        assert_eq!(a, Matrix::from_rows([[1, 2, 0], [0, 0, 1]]));
+    }
+}
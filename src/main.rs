@@ -1,56 +1,141 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, download, inputs, migrate, progress, read, scaffold, solve, start, wait,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{error::Error, path::PathBuf, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::template::{get_default_year, unlock};
+    use advent_of_code::{Part, PuzzleId, Year};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            id: PuzzleId,
+            force: bool,
         },
         Read {
-            day: Day,
+            id: PuzzleId,
+        },
+        Progress {
+            year: Year,
+            calendar: bool,
+        },
+        Inputs {
+            year: Year,
+            action: String,
+        },
+        Migrate {
+            year: Year,
+        },
+        Start {
+            id: PuzzleId,
+        },
+        Wait {
+            id: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            id: PuzzleId,
         },
         Solve {
-            day: Day,
+            id: PuzzleId,
             release: bool,
             time: bool,
-            submit: Option<u8>,
+            trace: bool,
+            viz: Option<PathBuf>,
+            submit: Option<Part>,
         },
         All {
+            year: Year,
             release: bool,
             time: bool,
         },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    const NO_YEAR: &str = "no year given, pass `--year <year>` or set AOC_YEAR.";
+    const NO_DAY: &str = "no day given and no puzzle is active today, puzzles unlock from December 1st to 25th (US Eastern time).";
+
+    /// Commands take an optional `--year`, falling back to `AOC_YEAR`.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => get_default_year().ok_or_else(|| NO_YEAR.into()),
+        }
+    }
+
+    fn parse_puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, Box<dyn Error>> {
+        let year = parse_year(args)?;
+        Ok(PuzzleId::new(year, args.free_from_str()?))
+    }
+
+    /// Like [`parse_puzzle`], but defaults to today's puzzle if no day is given.
+    /// An explicit `--year` still applies, `AOC_YEAR` does not.
+    fn parse_puzzle_or_current(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn Error>> {
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+
+        if let Some(day) = args.opt_free_from_str()? {
+            let year = year.or_else(get_default_year).ok_or(NO_YEAR)?;
+            return Ok(PuzzleId::new(year, day));
+        }
+
+        let current = unlock::current_puzzle().ok_or(NO_DAY)?;
+        Ok(PuzzleId::new(year.unwrap_or(current.year), current.day))
+    }
+
+    pub fn parse() -> Result<AppArguments, Box<dyn Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: parse_year(&mut args)?,
                 release: args.contains("--release"),
                 time: args.contains("--time"),
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                force: args.contains("--force"),
+                id: parse_puzzle_or_current(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                id: parse_puzzle_or_current(&mut args)?,
+            },
+            Some("progress") => AppArguments::Progress {
+                year: parse_year(&mut args)?,
+                calendar: args.contains("--calendar"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                id: parse_puzzle_or_current(&mut args)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+            Some("inputs") => AppArguments::Inputs {
+                year: parse_year(&mut args)?,
+                action: args.free_from_str()?,
             },
+            Some("migrate") => AppArguments::Migrate {
+                year: parse_year(&mut args)?,
+            },
+            Some("start") => AppArguments::Start {
+                id: parse_puzzle_or_current(&mut args)?,
+            },
+            Some("wait") => AppArguments::Wait {
+                id: parse_puzzle(&mut args)?,
+            },
+            Some("solve") => {
+                // flags are parsed before the optional day, so they are not mistaken for it.
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let time = args.contains("--time");
+                let trace = args.contains("--trace");
+                let viz = args.opt_value_from_str("--viz")?;
+                AppArguments::Solve {
+                    id: parse_puzzle_or_current(&mut args)?,
+                    release,
+                    time,
+                    trace,
+                    viz,
+                    submit,
+                }
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -77,16 +162,27 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                time,
+            } => all::handle(year, release, time),
+            AppArguments::Download { id, force } => download::handle(id, force),
+            AppArguments::Read { id } => read::handle(id),
+            AppArguments::Progress { year, calendar } => progress::handle(year, calendar),
+            AppArguments::Inputs { year, action } => inputs::handle(year, &action),
+            AppArguments::Migrate { year } => migrate::handle(year),
+            AppArguments::Start { id } => start::handle(id),
+            AppArguments::Wait { id } => wait::handle(id),
+            AppArguments::Scaffold { id } => scaffold::handle(id),
             AppArguments::Solve {
-                day,
+                id,
                 release,
                 time,
+                trace,
+                viz,
                 submit,
-            } => solve::handle(day, release, time, submit),
+            } => solve::handle(id, release, time, trace, viz, submit),
         },
     };
 }
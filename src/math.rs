@@ -0,0 +1,387 @@
+use num::{BigInt, BigRational, Integer, Signed, Zero};
+
+use crate::geometry::Point2;
+
+/// Returns the greatest common divisor, which is never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer + Copy>(mut a: T, mut b: T) -> T {
+    while !b.is_zero() {
+        (a, b) = (b, a % b);
+    }
+    if a < T::zero() {
+        T::zero() - a
+    } else {
+        a
+    }
+}
+
+/// Returns the least common multiple, which is never negative.
+pub fn lcm<T: Integer + Copy>(a: T, b: T) -> T {
+    if a.is_zero() || b.is_zero() {
+        return T::zero();
+    }
+    let l = a / gcd(a, b) * b;
+    if l < T::zero() {
+        T::zero() - l
+    } else {
+        l
+    }
+}
+
+/// Returns the least common multiple of all values, or 1 if there are none.
+pub fn lcm_all<T: Integer + Copy>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::one(), lcm)
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn extended_gcd<T: Integer + Signed + Copy>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::one(), T::zero());
+    let (mut old_t, mut t) = (T::zero(), T::one());
+    while !r.is_zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+    if old_r.is_negative() {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Returns the inverse of `a` modulo `m`, i.e. `x` in `0..m` with `a * x ≡ 1 (mod m)`,
+/// or [`None`] if `a` and `m` are not coprime.
+pub fn mod_inverse<T: Integer + Signed + Copy>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a.mod_floor(&m), m);
+    g.is_one().then(|| x.mod_floor(&m))
+}
+
+/// Returns `base^exp mod modulus`.
+#[must_use]
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    // the result is reduced modulo a u64.
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        result as u64
+    }
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the chinese remainder theorem.
+/// The moduli do not have to be coprime. Returns `(x, m)`, where `x` is the smallest non-negative
+/// solution and all solutions are `x + k * m`, or [`None`] if the congruences contradict each other.
+///
+/// ```
+/// # use advent_of_code::math::crt;
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+/// ```
+#[must_use]
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0i128, 1i128);
+    for &(residue, modulus) in congruences {
+        let (residue, modulus) = (i128::from(residue), i128::from(modulus));
+        let (g, p, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        x += m * (diff / g * p).mod_floor(&step);
+        m *= step;
+        x = x.mod_floor(&m);
+    }
+    Some((i64::try_from(x).ok()?, i64::try_from(m).ok()?))
+}
+
+/// Returns the largest integer whose square is at most `n`.
+#[must_use]
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method converges from above, starting at a power of two that is too large.
+    let mut x = 1u64 << (64 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn to_points(points: &[(i64, i64)]) -> Vec<(BigRational, BigRational)> {
+    points
+        .iter()
+        .map(|&(x, y)| {
+            (
+                BigRational::from_integer(BigInt::from(x)),
+                BigRational::from_integer(BigInt::from(y)),
+            )
+        })
+        .collect()
+}
+
+/// Evaluates the polynomial of lowest degree that passes through all points at `x`,
+/// using Lagrange's formula. The points must have distinct `x` coordinates.
+///
+/// ```
+/// # use advent_of_code::math::lagrange;
+/// # use num::{BigRational, BigInt};
+/// // y = x^2 + 1
+/// let points = [(0, 1), (1, 2), (3, 10)];
+/// assert_eq!(lagrange(&points, 10), BigRational::from_integer(BigInt::from(101)));
+/// ```
+#[must_use]
+pub fn lagrange(points: &[(i64, i64)], x: i64) -> BigRational {
+    let points = to_points(points);
+    let x = BigRational::from_integer(BigInt::from(x));
+
+    let mut sum = BigRational::zero();
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        let mut term = yi.clone();
+        for (j, (xj, _)) in points.iter().enumerate() {
+            if i != j {
+                term *= (&x - xj) / (xi - xj);
+            }
+        }
+        sum += term;
+    }
+    sum
+}
+
+/// Like [`lagrange`], but with Newton's divided differences. Both give the same result,
+/// this one is cheaper when evaluating the same points more than once.
+#[derive(Debug, Clone)]
+pub struct Newton {
+    xs: Vec<BigRational>,
+    coefficients: Vec<BigRational>,
+}
+
+impl Newton {
+    /// Computes the divided differences of the points. They must have distinct `x` coordinates.
+    #[must_use]
+    pub fn new(points: &[(i64, i64)]) -> Self {
+        let points = to_points(points);
+        let xs: Vec<_> = points.iter().map(|(x, _)| x.clone()).collect();
+        let mut coefficients: Vec<_> = points.into_iter().map(|(_, y)| y).collect();
+
+        for level in 1..xs.len() {
+            for i in (level..xs.len()).rev() {
+                coefficients[i] =
+                    (&coefficients[i] - &coefficients[i - 1]) / (&xs[i] - &xs[i - level]);
+            }
+        }
+
+        Self { xs, coefficients }
+    }
+
+    /// Evaluates the polynomial at `x`.
+    #[must_use]
+    pub fn eval(&self, x: i64) -> BigRational {
+        let x = BigRational::from_integer(BigInt::from(x));
+        // Horner's method over the nested form c0 + (x - x0) * (c1 + (x - x1) * (...)).
+        self.coefficients
+            .iter()
+            .zip(&self.xs)
+            .rev()
+            .fold(BigRational::zero(), |acc, (c, xi)| acc * (&x - xi) + c)
+    }
+}
+
+/// Converts an exact result to an [`i64`], if it is an integer in range.
+#[must_use]
+pub fn to_i64(value: &BigRational) -> Option<i64> {
+    if !value.is_integer() {
+        return None;
+    }
+    i64::try_from(value.to_integer()).ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns twice the signed area of a simple polygon with the shoelace formula.
+/// The sign depends on the orientation of the vertices.
+#[must_use]
+pub fn shoelace(vertices: &[Point2<i64>]) -> i64 {
+    closed_edges(vertices)
+        .map(|(a, b)| a.x * b.y - a.y * b.x)
+        .sum()
+}
+
+/// Returns the area of a simple polygon.
+#[must_use]
+pub fn polygon_area(vertices: &[Point2<i64>]) -> f64 {
+    // the doubled area of real puzzle polygons is far below 2^53.
+    #[allow(clippy::cast_precision_loss)]
+    {
+        shoelace(vertices).abs() as f64 / 2.0
+    }
+}
+
+/// Counts the lattice points on the edges of a polygon.
+#[must_use]
+pub fn boundary_points(vertices: &[Point2<i64>]) -> i64 {
+    closed_edges(vertices)
+        .map(|(a, b)| gcd(b.x - a.x, b.y - a.y))
+        .sum()
+}
+
+/// Counts the lattice points strictly inside a simple polygon with Pick's theorem.
+#[must_use]
+pub fn interior_points(vertices: &[Point2<i64>]) -> i64 {
+    (shoelace(vertices).abs() - boundary_points(vertices) + 2) / 2
+}
+
+/// Counts the lattice points inside or on a simple polygon, e.g. the tiles of a dug out lagoon.
+#[must_use]
+pub fn lattice_points(vertices: &[Point2<i64>]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+fn closed_edges(vertices: &[Point2<i64>]) -> impl Iterator<Item = (Point2<i64>, Point2<i64>)> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use num::{BigInt, BigRational};
+
+    use super::{
+        boundary_points, crt, extended_gcd, gcd, interior_points, isqrt, lagrange, lattice_points,
+        lcm, lcm_all, mod_inverse, mod_pow, polygon_area, shoelace, to_i64, Newton,
+    };
+    use crate::geometry::Point2;
+
+    fn rational(n: i64) -> BigRational {
+        BigRational::from_integer(BigInt::from(n))
+    }
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 5u32), 5);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u64>::new()), 1);
+    }
+
+    #[test]
+    fn computes_extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (17, 5), (0, 7), (7, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(2, 4), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(2, 0, 7), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+        assert_eq!(
+            mod_pow(u64::MAX, u64::MAX, 1_000_000_007),
+            mod_pow(u64::MAX % 1_000_000_007, u64::MAX, 1_000_000_007)
+        );
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(
+            crt(&[(0, 1_000_000_007), (1, 998_244_353)]),
+            Some((993_328_913_953_302_349, 998_244_359_987_710_471))
+        );
+    }
+
+    #[test]
+    fn computes_integer_square_roots() {
+        for n in 0..10_000 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+        }
+        assert_eq!(isqrt(u64::MAX), u64::from(u32::MAX));
+        assert_eq!(isqrt(1 << 62), 1 << 31);
+    }
+
+    #[test]
+    fn interpolates() {
+        // y = 2x^2 - 3x + 1
+        let points = [(0, 1), (1, 0), (2, 3)];
+        assert_eq!(lagrange(&points, 5), rational(36));
+        assert_eq!(lagrange(&points, -1), rational(6));
+        assert_eq!(Newton::new(&points).eval(5), rational(36));
+        assert_eq!(
+            Newton::new(&[(4, 21), (1, 0), (2, 3)]).eval(-1),
+            rational(6)
+        );
+
+        // a line through (0, 0) and (2, 1) has a fractional value at 1.
+        let half = lagrange(&[(0, 0), (2, 1)], 1);
+        assert_eq!(half, BigRational::new(BigInt::from(1), BigInt::from(2)));
+        assert_eq!(to_i64(&half), None);
+        assert_eq!(to_i64(&rational(-7)), Some(-7));
+    }
+
+    #[test]
+    fn interpolates_without_overflow() {
+        // y = x^3 does not fit into 64 bit at x = 3_000_000.
+        let points = [(0, 0), (1, 1), (2, 8), (3, 27)];
+        let expected = BigRational::from_integer(BigInt::from(3_000_000).pow(3));
+        assert_eq!(Newton::new(&points).eval(3_000_000), expected);
+        assert_eq!(lagrange(&points, 3_000_000), expected);
+        assert_eq!(to_i64(&expected), None);
+    }
+
+    #[test]
+    fn measures_polygons() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)].map(Point2::from);
+        assert_eq!(shoelace(&square).abs(), 32);
+        assert_eq!(polygon_area(&square), 16.0);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+        assert_eq!(lattice_points(&square), 25);
+
+        // reversing the vertices flips the sign of the area.
+        let mut reversed = square;
+        reversed.reverse();
+        assert_eq!(shoelace(&reversed), -shoelace(&square));
+
+        let triangle = [(0, 0), (3, 0), (0, 3)].map(Point2::from);
+        assert_eq!(polygon_area(&triangle), 4.5);
+        assert_eq!(boundary_points(&triangle), 9);
+        assert_eq!(interior_points(&triangle), 1);
+    }
+}
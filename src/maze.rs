@@ -0,0 +1,280 @@
+use crate::geometry::Dir4;
+use crate::grid::Grid;
+
+/// A corridor of a maze, leading from one junction to another without any choices on the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Corridor {
+    pub to: usize,
+    /// The number of steps from one junction to the other.
+    pub length: usize,
+    /// The cells on the way, excluding the junction it starts at and including the one it ends at.
+    pub cells: Vec<(usize, usize)>,
+}
+
+/// A maze on a grid, contracted to the junctions where paths fork and the corridors between them.
+///
+/// Longest paths are searched with a bitmask of visited junctions, so there may be at most 64.
+///
+/// ```
+/// # use advent_of_code::grid::Grid;
+/// # use advent_of_code::maze::JunctionGraph;
+/// let grid: Grid<char> = "#.###\n#...#\n#.#.#\n#...#\n###.#".parse().unwrap();
+/// let maze = JunctionGraph::from_grid(&grid, &[(1, 0), (3, 4)], |&c| c != '#', |_| None);
+/// let (start, end) = (maze.junction((1, 0)).unwrap(), maze.junction((3, 4)).unwrap());
+/// let (length, path) = maze.longest_path(start, end).unwrap();
+/// assert_eq!(length, 6);
+/// assert_eq!(maze.cells(&path).len(), 7);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JunctionGraph {
+    junctions: Vec<(usize, usize)>,
+    corridors: Vec<Vec<Corridor>>,
+}
+
+impl JunctionGraph {
+    /// Contracts the open cells of the grid. Junctions are the `endpoints` and every cell with
+    /// more than two open neighbours. Cells for which `one_way` returns a direction may only be
+    /// left in that direction, like the slopes of 2023 day 23.
+    pub fn from_grid<T>(
+        grid: &Grid<T>,
+        endpoints: &[(usize, usize)],
+        is_open: impl Fn(&T) -> bool,
+        one_way: impl Fn(&T) -> Option<Dir4>,
+    ) -> Self {
+        let open_neighbors = |pos| grid.neighbors4(pos).filter(|&next| is_open(&grid[next]));
+        let can_move = |from: (usize, usize), to: (usize, usize)| match one_way(&grid[from]) {
+            Some(dir) => grid.offset(from, dir.offset::<isize>().into()) == Some(to),
+            None => true,
+        };
+
+        let mut junctions = endpoints.to_vec();
+        junctions.extend(grid.positions().filter(|&pos| {
+            is_open(&grid[pos]) && !endpoints.contains(&pos) && open_neighbors(pos).count() > 2
+        }));
+
+        let corridors = junctions
+            .iter()
+            .map(|&junction| {
+                open_neighbors(junction)
+                    .filter(|&next| can_move(junction, next))
+                    .filter_map(|first| {
+                        // follow the corridor until it reaches a junction or a dead end.
+                        let (mut previous, mut current) = (junction, first);
+                        let mut cells = vec![current];
+                        loop {
+                            if let Some(to) = junctions.iter().position(|&j| j == current) {
+                                let length = cells.len();
+                                return Some(Corridor { to, length, cells });
+                            }
+                            let next = open_neighbors(current)
+                                .find(|&next| next != previous && can_move(current, next))?;
+                            (previous, current) = (current, next);
+                            cells.push(current);
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            junctions,
+            corridors,
+        }
+    }
+
+    /// Returns the positions of the junctions, starting with the endpoints.
+    #[must_use]
+    pub fn junctions(&self) -> &[(usize, usize)] {
+        &self.junctions
+    }
+
+    /// Returns the index of the junction at the position.
+    #[must_use]
+    pub fn junction(&self, pos: (usize, usize)) -> Option<usize> {
+        self.junctions.iter().position(|&j| j == pos)
+    }
+
+    /// Returns the corridors leaving the junction.
+    #[must_use]
+    pub fn corridors(&self, junction: usize) -> &[Corridor] {
+        &self.corridors[junction]
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.junctions.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.junctions.is_empty()
+    }
+
+    /// Finds the longest path between two junctions that visits no junction twice,
+    /// returning its length and the junctions on it.
+    ///
+    /// # Panics
+    /// Panics if the maze has more than 64 junctions.
+    #[must_use]
+    pub fn longest_path(&self, from: usize, to: usize) -> Option<(usize, Vec<usize>)> {
+        assert!(self.len() <= 64, "at most 64 junctions are supported");
+
+        let longest_exit: Vec<usize> = self
+            .corridors
+            .iter()
+            .map(|corridors| corridors.iter().map(|c| c.length).max().unwrap_or(0))
+            .collect();
+        // if the target can only be entered from one junction, the path has to go there directly.
+        let mut entries = (0..self.len()).filter(|&j| self.corridors[j].iter().any(|c| c.to == to));
+        let last = entries.next().filter(|_| entries.next().is_none());
+
+        let mut search = LongestPath {
+            maze: self,
+            to,
+            last,
+            longest_exit,
+            path: vec![from],
+            best: None,
+        };
+        let budget = search.longest_exit.iter().sum::<usize>() - search.longest_exit[from];
+        search.visit(from, 1 << from, 0, budget);
+        search.best
+    }
+
+    /// Returns the cells along a path of junctions, starting with the first junction.
+    #[must_use]
+    pub fn cells(&self, path: &[usize]) -> Vec<(usize, usize)> {
+        let mut cells: Vec<_> = path
+            .first()
+            .map(|&j| self.junctions[j])
+            .into_iter()
+            .collect();
+        for (&from, &to) in path.iter().zip(path.iter().skip(1)) {
+            let corridor = self.corridors[from]
+                .iter()
+                .filter(|c| c.to == to)
+                .max_by_key(|c| c.length)
+                .expect("consecutive junctions are connected");
+            cells.extend(&corridor.cells);
+        }
+        cells
+    }
+}
+
+/// The state of a depth-first search for the longest path.
+struct LongestPath<'a> {
+    maze: &'a JunctionGraph,
+    to: usize,
+    last: Option<usize>,
+    longest_exit: Vec<usize>,
+    path: Vec<usize>,
+    best: Option<(usize, Vec<usize>)>,
+}
+
+impl LongestPath<'_> {
+    /// `budget` is the sum of the longest exits of all unvisited junctions,
+    /// which bounds the length the rest of the path can add.
+    fn visit(&mut self, current: usize, visited: u64, length: usize, budget: usize) {
+        if current == self.to {
+            if self.best.as_ref().is_none_or(|(best, _)| length > *best) {
+                self.best = Some((length, self.path.clone()));
+            }
+            return;
+        }
+        let bound = length + self.longest_exit[current] + budget;
+        if self.best.as_ref().is_some_and(|(best, _)| bound <= *best) {
+            return;
+        }
+
+        let maze = self.maze;
+        for corridor in &maze.corridors[current] {
+            let next = corridor.to;
+            if visited & (1 << next) != 0 || (Some(current) == self.last && next != self.to) {
+                continue;
+            }
+            self.path.push(next);
+            self.visit(
+                next,
+                visited | (1 << next),
+                length + corridor.length,
+                budget - self.longest_exit[next],
+            );
+            self.path.pop();
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::JunctionGraph;
+    use crate::geometry::Dir4;
+    use crate::grid::Grid;
+
+    const MAZE: &str = "\
+#.#####
+#.....#
+#.###.#
+#.....#
+#####.#";
+
+    fn maze(slopes: bool) -> JunctionGraph {
+        let mut grid: Grid<char> = MAZE.parse().unwrap();
+        if slopes {
+            grid[(3, 1)] = '>';
+            grid[(3, 3)] = '<';
+        }
+        JunctionGraph::from_grid(
+            &grid,
+            &[(1, 0), (5, 4)],
+            |&c| c != '#',
+            |&c| Dir4::try_from(c).ok(),
+        )
+    }
+
+    #[test]
+    fn contracts_corridors() {
+        let maze = maze(false);
+        assert_eq!(maze.junctions(), [(1, 0), (5, 4), (1, 1), (5, 3)]);
+        assert_eq!(maze.len(), 4);
+
+        let corridors = maze.corridors(2);
+        let mut lengths: Vec<_> = corridors.iter().map(|c| (c.to, c.length)).collect();
+        lengths.sort_unstable();
+        assert_eq!(lengths, [(0, 1), (3, 6), (3, 6)]);
+    }
+
+    #[test]
+    fn finds_longest_paths() {
+        let maze = maze(false);
+        let (start, end) = (0, 1);
+        let (length, path) = maze.longest_path(start, end).unwrap();
+        assert_eq!(length, 1 + 6 + 1);
+        assert_eq!(path, [0, 2, 3, 1]);
+
+        let cells = maze.cells(&path);
+        assert_eq!(cells.len(), length + 1);
+        assert_eq!(cells.first(), Some(&(1, 0)));
+        assert_eq!(cells.last(), Some(&(5, 4)));
+        assert!(cells
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+    }
+
+    #[test]
+    fn honours_slopes() {
+        let maze = maze(true);
+        // the upper corridor may only be walked to the east, the lower one to the west.
+        let corridors = maze.corridors(2);
+        assert_eq!(corridors.len(), 2);
+        assert!(maze
+            .corridors(3)
+            .iter()
+            .all(|c| c.to != 2 || c.cells.contains(&(3, 3))));
+        assert_eq!(maze.longest_path(0, 1).map(|(length, _)| length), Some(8));
+
+        let (length, path) = maze.longest_path(1, 0).unwrap();
+        assert_eq!(length, 8);
+        let cells = maze.cells(&path);
+        assert!(cells.contains(&(3, 3)) && !cells.contains(&(3, 1)));
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::ops::AddAssign;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Lookups of every [`Memo`] dropped since the last [`take_stats`].
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// A cache of function results, owned by a single solve.
+///
+/// Unlike a process-global cache, it is dropped when the solve returns, so repeated runs
+/// start cold and memory does not pile up. Recursive functions take it as a parameter:
+///
+/// ```
+/// # use advent_of_code::memo::Memo;
+/// fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+///     if n < 2 {
+///         return n;
+///     }
+///     if let Some(result) = memo.get(&n) {
+///         return result;
+///     }
+///     let result = fibonacci(n - 1, memo) + fibonacci(n - 2, memo);
+///     memo.insert(n, result)
+/// }
+///
+/// let mut memo = Memo::new();
+/// assert_eq!(fibonacci(80, &mut memo), 23_416_728_348_467_685);
+/// assert_eq!(memo.stats().misses, 79);
+/// ```
+///
+/// When dropped, it adds its hits and misses to the totals returned by [`take_stats`].
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V, ahash::RandomState>,
+    stats: MemoStats,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::default(),
+            stats: MemoStats::default(),
+        }
+    }
+
+    /// Returns the cached result for the key, counting the lookup as a hit or a miss.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let result = self.cache.get(key).cloned();
+        if result.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+        result
+    }
+
+    /// Caches the result for the key and returns it.
+    pub fn insert(&mut self, key: K, value: V) -> V {
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the cached result for the key, computing it with `f` on a miss.
+    ///
+    /// `f` cannot use the memo itself; recursive functions combine [`Memo::get`] and
+    /// [`Memo::insert`] instead.
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce() -> V) -> V {
+        match self.get(&key) {
+            Some(value) => value,
+            None => self.insert(key, f()),
+        }
+    }
+
+    /// Forgets all results, but keeps counting lookups.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<K, V> Memo<K, V> {
+    /// Returns the lookups made so far.
+    #[must_use]
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    /// Returns the number of cached results.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        HITS.fetch_add(self.stats.hits, Ordering::Relaxed);
+        MISSES.fetch_add(self.stats.misses, Ordering::Relaxed);
+    }
+}
+
+/// Counts the lookups of one or more [`Memo`]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl MemoStats {
+    #[must_use]
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Returns the share of lookups that were hits, or [`None`] without lookups.
+    #[must_use]
+    pub fn hit_rate(&self) -> Option<f64> {
+        #[allow(clippy::cast_precision_loss)]
+        (self.lookups() > 0).then(|| self.hits as f64 / self.lookups() as f64)
+    }
+}
+
+impl AddAssign for MemoStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.hits += rhs.hits;
+        self.misses += rhs.misses;
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.hit_rate() {
+            Some(rate) => write!(
+                f,
+                "{:.1}% memo hits of {} lookups",
+                rate * 100.0,
+                self.lookups()
+            ),
+            None => write!(f, "no memo lookups"),
+        }
+    }
+}
+
+/// Returns the lookups of all memos dropped since the last call, and starts counting anew.
+pub fn take_stats() -> MemoStats {
+    MemoStats {
+        hits: HITS.swap(0, Ordering::Relaxed),
+        misses: MISSES.swap(0, Ordering::Relaxed),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Memo, MemoStats};
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let mut memo = Memo::new();
+        assert_eq!(memo.get_or_insert_with("a", || 1), 1);
+        assert_eq!(memo.get_or_insert_with("a", || 2), 1);
+        assert_eq!(memo.get(&"b"), None);
+        assert_eq!(memo.len(), 1);
+        assert_eq!(memo.stats(), MemoStats { hits: 1, misses: 2 });
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.get_or_insert_with("a", || 3), 3);
+        assert_eq!(memo.stats().misses, 3);
+    }
+
+    #[test]
+    fn reports_hit_rates() {
+        let stats = MemoStats { hits: 3, misses: 1 };
+        assert_eq!(stats.hit_rate(), Some(0.75));
+        assert_eq!(stats.to_string(), "75.0% memo hits of 4 lookups");
+        assert_eq!(MemoStats::default().hit_rate(), None);
+
+        let mut total = stats;
+        total += MemoStats { hits: 1, misses: 3 };
+        assert_eq!(total, MemoStats { hits: 4, misses: 4 });
+    }
+}
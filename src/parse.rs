@@ -0,0 +1,436 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
+
+/// An error which can be returned by the parsing helpers, with the position of the offending text.
+///
+/// Errors are raised for the text that failed to parse, so their position is relative to
+/// that text. [`ParseError::locate`] moves it into the enclosing input, which [`lines`]
+/// and [`blocks`] do automatically.
+#[derive(Clone)]
+pub struct ParseError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: usize,
+    pub kind: ParseErrorKind,
+    source_line: String,
+    width: usize,
+    /// The address of the offending text, used to locate it in an enclosing input.
+    address: usize,
+}
+
+/// The reason a [`ParseError`] was raised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The text does not contain the expected separator or token.
+    Expected(String),
+    /// The text is not a valid number.
+    InvalidNumber(String),
+    /// The text does not have the expected number of fields.
+    WrongCount { expected: usize, found: usize },
+    /// The text is well-formed, but its value is not accepted.
+    Invalid(String),
+}
+
+impl ParseError {
+    /// Creates an error for `at`, which should be a slice of the input being parsed.
+    #[must_use]
+    pub fn new(at: &str, kind: ParseErrorKind) -> Self {
+        let source_line = at.lines().next().unwrap_or_default().to_owned();
+        Self {
+            line: 1,
+            column: 1,
+            kind,
+            width: source_line.chars().count(),
+            source_line,
+            address: at.as_ptr() as usize,
+        }
+    }
+
+    /// Creates an [`ParseErrorKind::Invalid`] error for `at` with the message.
+    #[must_use]
+    pub fn invalid(at: &str, message: impl Into<String>) -> Self {
+        Self::new(at, ParseErrorKind::Invalid(message.into()))
+    }
+
+    /// Moves the position of the error into `input`, if the offending text is a slice of it.
+    /// Errors for text outside of `input` are returned unchanged.
+    #[must_use]
+    pub fn locate(mut self, input: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        if self.address < start || self.address > start + input.len() {
+            return self;
+        }
+
+        let prefix = &input[..self.address - start];
+        let line_start = prefix.rfind('\n').map_or(0, |i| i + 1);
+        self.line = prefix.matches('\n').count() + 1;
+        self.column = prefix[line_start..].chars().count() + 1;
+        self.source_line = input[line_start..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_owned();
+        self
+    }
+
+    /// Returns the line of the input that contains the error.
+    #[must_use]
+    pub fn source_line(&self) -> &str {
+        &self.source_line
+    }
+}
+
+impl PartialEq for ParseError {
+    fn eq(&self, other: &Self) -> bool {
+        (self.line, self.column, &self.kind) == (other.line, other.column, &other.kind)
+    }
+}
+
+impl Eq for ParseError {}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::Expected(token) => write!(f, "expected {token:?}"),
+            ParseErrorKind::InvalidNumber(text) => write!(f, "{text:?} is not a valid number"),
+            ParseErrorKind::WrongCount { expected, found } => {
+                write!(f, "expected {expected} fields, found {found}")
+            }
+            ParseErrorKind::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Display for ParseError {
+    /// Renders the error with the offending line and a caret below the position.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let remaining = self
+            .source_line
+            .chars()
+            .count()
+            .saturating_sub(self.column - 1);
+        let carets = "^".repeat(self.width.clamp(1, remaining.max(1)));
+
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{number} | {}", self.source_line)?;
+        write!(f, "{gutter} | {}{carets}", " ".repeat(self.column - 1))
+    }
+}
+
+impl Debug for ParseError {
+    /// Shows the diagnostic, so unwrapping a result reports the position of the error.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\n{self}")
+    }
+}
+
+impl Error for ParseError {}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses every line of the input, stopping at the first error.
+pub fn lines<'a, T>(
+    input: &'a str,
+    f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(f)
+        .collect::<Result<_, _>>()
+        .map_err(|err| err.locate(input))
+}
+
+/// Parses the whole input with its [`FromStr`] implementation, locating errors in the input.
+pub fn from_str<T: FromStr<Err = ParseError>>(input: &str) -> Result<T, ParseError> {
+    input.parse().map_err(|err: ParseError| err.locate(input))
+}
+
+/// Splits the input into blocks that are separated by blank lines.
+pub fn split_blocks(input: &str) -> impl Iterator<Item = &str> {
+    let input = input.trim_matches('\n');
+    let mut rest = Some(input);
+    std::iter::from_fn(move || {
+        let text = rest?;
+        match text.find("\n\n") {
+            Some(end) => {
+                rest = Some(text[end..].trim_start_matches('\n'));
+                Some(&text[..end])
+            }
+            None => {
+                rest = None;
+                Some(text)
+            }
+        }
+    })
+    .filter(|block| !block.is_empty())
+}
+
+/// Parses every block of the input, stopping at the first error.
+pub fn blocks<'a, T>(
+    input: &'a str,
+    f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    split_blocks(input)
+        .map(f)
+        .collect::<Result<_, _>>()
+        .map_err(|err| err.locate(input))
+}
+
+/// Parses the trimmed text as a number.
+pub fn int<T: FromStr>(text: &str) -> Result<T, ParseError> {
+    let trimmed = text.trim();
+    trimmed
+        .parse()
+        .map_err(|_| ParseError::new(trimmed, ParseErrorKind::InvalidNumber(trimmed.to_owned())))
+}
+
+/// Extracts all integers from the text, ignoring everything between them.
+/// A `-` directly before a number is a sign, unless it follows a digit, as in `1-3`.
+pub fn ints<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    number_spans(text, true).map(int).collect()
+}
+
+/// Extracts all unsigned integers from the text, treating every `-` as a separator.
+pub fn uints<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    number_spans(text, false).map(int).collect()
+}
+
+/// Extracts exactly `N` integers from the text.
+pub fn ints_n<T: FromStr, const N: usize>(text: &str) -> Result<[T; N], ParseError> {
+    let values = ints(text)?;
+    let found = values.len();
+    values
+        .try_into()
+        .map_err(|_| ParseError::new(text, ParseErrorKind::WrongCount { expected: N, found }))
+}
+
+fn number_spans(text: &str, signed: bool) -> impl Iterator<Item = &str> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let is_sign = signed
+                && bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+            if is_sign || bytes[i].is_ascii_digit() {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                return Some(&text[start..i]);
+            }
+            i += 1;
+        }
+        None
+    })
+}
+
+/// Splits the text at the first occurrence of `separator`.
+pub fn split_once<'a>(text: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(separator)
+        .ok_or_else(|| ParseError::new(&text[text.len()..], expected(separator)))
+}
+
+/// Splits text like `key: value` into its trimmed key and value.
+pub fn key_value<'a>(text: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+    let (key, value) = split_once(text, separator)?;
+    Ok((key.trim(), value.trim()))
+}
+
+/// Splits text like `x=1,m=2` into trimmed key/value pairs.
+pub fn key_values<'a>(
+    text: &'a str,
+    pair_separator: &str,
+    separator: &str,
+) -> Result<Vec<(&'a str, &'a str)>, ParseError> {
+    text.split(pair_separator)
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| key_value(pair.trim(), separator))
+        .collect()
+}
+
+/// Splits text like `a -> b, c` into its source and trimmed targets.
+pub fn arrow_list(text: &str) -> Result<(&str, Vec<&str>), ParseError> {
+    let (source, targets) = key_value(text, "->")?;
+    let targets = targets
+        .split(',')
+        .map(str::trim)
+        .filter(|target| !target.is_empty())
+        .collect();
+    Ok((source, targets))
+}
+
+/// Splits the text into fields of the given widths in characters, trimming each of them.
+/// The last field may be shorter than its width.
+pub fn fixed_width<'a>(text: &'a str, widths: &[usize]) -> Result<Vec<&'a str>, ParseError> {
+    let mut fields = Vec::with_capacity(widths.len());
+    let mut rest = text;
+    for (i, &width) in widths.iter().enumerate() {
+        let end = rest
+            .char_indices()
+            .nth(width)
+            .map_or(rest.len(), |(end, _)| end);
+        if end == 0 && width > 0 {
+            return Err(ParseError::new(
+                rest,
+                ParseErrorKind::WrongCount {
+                    expected: widths.len(),
+                    found: i,
+                },
+            ));
+        }
+        fields.push(rest[..end].trim());
+        rest = &rest[end..];
+    }
+    Ok(fields)
+}
+
+/// Returns the token with the given name, or an error for the text.
+pub fn one_of<T: Copy>(text: &str, tokens: &[(&str, T)]) -> Result<T, ParseError> {
+    let trimmed = text.trim();
+    tokens
+        .iter()
+        .find(|(token, _)| *token == trimmed)
+        .map(|&(_, value)| value)
+        .ok_or_else(|| {
+            let names = tokens.iter().map(|(token, _)| *token).collect::<Vec<_>>();
+            ParseError::new(trimmed, expected(&format!("one of {}", names.join(", "))))
+        })
+}
+
+/// Returns the character at `(x, y)` of the text, counted from 0, as a slice to raise errors for.
+/// Positions outside of the text return the empty slice at its end.
+#[must_use]
+pub fn char_at(text: &str, (x, y): (usize, usize)) -> &str {
+    text.lines()
+        .nth(y)
+        .and_then(|line| {
+            let (i, c) = line.char_indices().nth(x)?;
+            Some(&line[i..i + c.len_utf8()])
+        })
+        .unwrap_or(&text[text.len()..])
+}
+
+fn expected(token: &str) -> ParseErrorKind {
+    ParseErrorKind::Expected(token.to_owned())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        arrow_list, blocks, char_at, fixed_width, int, ints, ints_n, key_value, key_values, lines,
+        one_of, split_blocks, split_once, uints, ParseError, ParseErrorKind,
+    };
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(ints::<i64>("x=-5, y=12 @ -3"), Ok(vec![-5, 12, -3]));
+        assert_eq!(ints::<i32>("1-3 and 4--2"), Ok(vec![1, 3, 4, -2]));
+        assert_eq!(uints::<u32>("1-3 and -2"), Ok(vec![1, 3, 2]));
+        assert_eq!(ints::<u8>("no numbers"), Ok(vec![]));
+        assert_eq!(
+            ints_n::<i32, 3>("1,2,3~4").unwrap_err().kind,
+            ParseErrorKind::WrongCount {
+                expected: 3,
+                found: 4
+            }
+        );
+        assert_eq!(ints_n::<i32, 2>("1,2"), Ok([1, 2]));
+        assert_eq!(int::<u32>(" 42 "), Ok(42));
+    }
+
+    #[test]
+    fn locates_errors() {
+        let input = "1 2\n3 400\n5 6";
+        let err = lines(input, ints::<u8>).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.kind, ParseErrorKind::InvalidNumber("400".to_owned()));
+        assert_eq!(err.source_line(), "3 400");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: \"400\" is not a valid number\n  |\n2 | 3 400\n  |   ^^^"
+        );
+    }
+
+    #[test]
+    fn locates_errors_in_blocks() {
+        let input = "a: 1\nb: 2\n\nc: 3\nd 4\n";
+        let err = blocks(input, |block| {
+            lines(block, |line| {
+                key_value(line, ":").map(|(k, _)| k.to_owned())
+            })
+        })
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (5, 4));
+        assert_eq!(err.kind, ParseErrorKind::Expected(":".to_owned()));
+        assert!(err.to_string().ends_with("5 | d 4\n  |    ^"));
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "\na\nb\n\n\nc\n\nd\n";
+        assert_eq!(split_blocks(input).collect::<Vec<_>>(), ["a\nb", "c", "d"]);
+        assert_eq!(split_blocks("").count(), 0);
+    }
+
+    #[test]
+    fn splits_keys_and_values() {
+        assert_eq!(split_once("a = b", " = "), Ok(("a", "b")));
+        assert_eq!(key_value("Game 1: 3 blue", ":"), Ok(("Game 1", "3 blue")));
+        assert_eq!(
+            key_values("x=787, m=2655,", ",", "="),
+            Ok(vec![("x", "787"), ("m", "2655")])
+        );
+        assert_eq!(arrow_list("%a -> b, c"), Ok(("%a", vec!["b", "c"])));
+        assert_eq!(
+            arrow_list("broadcaster => a").unwrap_err().kind,
+            ParseErrorKind::Expected("->".to_owned())
+        );
+    }
+
+    #[test]
+    fn splits_fixed_width_fields() {
+        assert_eq!(
+            fixed_width("ab  12 x", &[4, 3, 5]),
+            Ok(vec!["ab", "12", "x"])
+        );
+        assert_eq!(
+            fixed_width("ab", &[2, 2]).unwrap_err().kind,
+            ParseErrorKind::WrongCount {
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn finds_characters() {
+        let text = "ab\ncd";
+        let err = ParseError::invalid(char_at(text, (1, 1)), "bad cell").locate(text);
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(char_at(text, (5, 0)), "");
+    }
+
+    #[test]
+    fn matches_tokens() {
+        let tokens = [("red", 0), ("green", 1), ("blue", 2)];
+        assert_eq!(one_of(" blue", &tokens), Ok(2));
+        assert_eq!(
+            one_of("pink", &tokens).unwrap_err().kind,
+            ParseErrorKind::Expected("one of red, green, blue".to_owned())
+        );
+    }
+}
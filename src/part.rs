@@ -0,0 +1,153 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// One of the two parts of a puzzle.
+///
+/// # Display
+/// This value displays as its number.
+///
+/// ```
+/// # use advent_of_code::Part;
+/// assert_eq!(Part::Two.to_string(), "2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// Creates a [`Part`] from the provided number if it's 1 or 2,
+    /// returns [`None`] otherwise.
+    pub fn new(part: u8) -> Option<Self> {
+        match part {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(part: u8) -> Self {
+        if part == 1 {
+            Part::One
+        } else {
+            Part::Two
+        }
+    }
+
+    /// Converts the [`Part`] into its number.
+    pub fn into_inner(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    /// The part that is not `self`.
+    #[must_use]
+    pub fn other(self) -> Self {
+        match self {
+            Part::One => Part::Two,
+            Part::Two => Part::One,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+impl PartialEq<u8> for Part {
+    fn eq(&self, other: &u8) -> bool {
+        self.into_inner().eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = s.parse().map_err(|_| PartFromStrError)?;
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a part number of 1 or 2")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator that yields both parts of a puzzle.
+pub fn all_parts() -> std::array::IntoIter<Part, 2> {
+    Part::ALL.into_iter()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Part`] value in a const context.
+#[macro_export]
+macro_rules! part {
+    ($part:expr) => {{
+        const _ASSERT: () = assert!(
+            $part == 1 || $part == 2,
+            concat!("invalid part number `", $part, "`, expecting 1 or 2"),
+        );
+        $crate::Part::__new_unchecked($part)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{all_parts, Part};
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+        assert!("0".parse::<Part>().is_err());
+        assert!("7".parse::<Part>().is_err());
+        assert!("one".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn round_trips_numbers() {
+        for part in all_parts() {
+            assert_eq!(Part::new(part.into_inner()), Some(part));
+            assert_eq!(part.to_string(), part.into_inner().to_string());
+        }
+    }
+
+    #[test]
+    fn iterates_parts() {
+        assert_eq!(all_parts().collect::<Vec<_>>(), vec![Part::One, Part::Two]);
+        assert_eq!(Part::One.other(), Part::Two);
+        assert_eq!(Part::Two.other(), Part::One);
+    }
+
+    #[test]
+    fn creates_parts_in_const_context() {
+        const PART: Part = part!(2);
+        assert_eq!(PART, Part::Two);
+    }
+}
@@ -0,0 +1,29 @@
+//! The imports most solutions need, for `use advent_of_code::prelude::*;`.
+//!
+//! `HashMap` and `HashSet` are the standard collections with the `ahash` hasher, which is much
+//! faster than the default `SipHash`. Being the standard types, they can be collected from
+//! parallel iterators, but they are created with `default()` instead of `new()`.
+//!
+//! ```
+//! use advent_of_code::prelude::*;
+//!
+//! let mut counts = HashMap::default();
+//! for c in "abca".chars() {
+//!     *counts.entry(c).or_insert(0) += 1;
+//! }
+//! assert_eq!(counts[&'a'], 2);
+//! let squares: HashSet<u32> = (1..4u32).into_par_iter().map(|n| n * n).collect();
+//! assert_eq!(squares.iter().sorted().collect_vec(), [&1, &4, &9]);
+//! ```
+
+pub use std::collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque};
+
+pub use ahash::{AHashMap, AHashSet};
+pub use itertools::Itertools;
+pub use rayon::prelude::*;
+
+/// The default hash map, using the `ahash` hasher.
+pub type HashMap<K, V> = std::collections::HashMap<K, V, ahash::RandomState>;
+
+/// The default hash set, using the `ahash` hasher.
+pub type HashSet<T> = std::collections::HashSet<T, ahash::RandomState>;
@@ -0,0 +1,434 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use indexmap::map::Entry;
+use indexmap::IndexMap;
+use num::Zero;
+
+/// Marks the start nodes, which have no parent.
+const NO_PARENT: usize = usize::MAX;
+
+/// The outcome of a search: the cost of every node that was reached, how it was reached,
+/// and the goal if one was found.
+///
+/// ```
+/// # use advent_of_code::search::bfs;
+/// let result = bfs([0], |&n: &i32| [n + 1, n * 2], |&n| n == 10);
+/// assert_eq!(result.cost(), Some(5));
+/// assert_eq!(result.path(), Some(vec![0, 1, 2, 4, 5, 10]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C> {
+    /// Maps every reached node to the index of its parent and its cost.
+    nodes: IndexMap<N, (usize, C)>,
+    goal: Option<usize>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> SearchResult<N, C> {
+    fn new() -> Self {
+        Self {
+            nodes: IndexMap::new(),
+            goal: None,
+        }
+    }
+
+    /// Returns the goal that ended the search, if any.
+    #[must_use]
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.map(|i| self.nodes.get_index(i).unwrap().0)
+    }
+
+    /// Returns the cost of reaching the goal.
+    #[must_use]
+    pub fn cost(&self) -> Option<C> {
+        self.goal.map(|i| self.nodes[i].1)
+    }
+
+    /// Returns the path from a start to the goal, both included.
+    #[must_use]
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goal.map(|i| self.path_from_index(i))
+    }
+
+    /// Returns the cost of reaching a node. For nodes that were not finalized
+    /// before the search stopped, this is only an upper bound.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.nodes.get(node).map(|&(_, cost)| cost)
+    }
+
+    /// Returns the path from a start to a reached node, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.nodes
+            .get_index_of(node)
+            .map(|i| self.path_from_index(i))
+    }
+
+    fn path_from_index(&self, mut i: usize) -> Vec<N> {
+        let mut path = vec![];
+        while i != NO_PARENT {
+            let (node, &(parent, _)) = self.nodes.get_index(i).unwrap();
+            path.push(node.clone());
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Iterates every reached node with its cost, in the order they were discovered.
+    pub fn distances(&self) -> impl Iterator<Item = (&N, C)> {
+        self.nodes.iter().map(|(node, &(_, cost))| (node, cost))
+    }
+
+    /// Returns the number of reached nodes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns true if the node was reached.
+    pub fn contains(&self, node: &N) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    fn insert_starts(&mut self, starts: impl IntoIterator<Item = N>, zero: C) -> Vec<usize> {
+        starts
+            .into_iter()
+            .map(|start| self.nodes.insert_full(start, (NO_PARENT, zero)).0)
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Breadth-first search from several starts, stopping at the first node matching `is_goal`.
+/// The cost of a node is the number of steps to reach it.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    bfs_limited(starts, successors, is_goal, usize::MAX)
+}
+
+/// Breadth-first search that visits every node within `max_depth` steps of a start,
+/// e.g. to build a distance map.
+pub fn bfs_distances<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    max_depth: usize,
+) -> SearchResult<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    bfs_limited(starts, successors, |_| false, max_depth)
+}
+
+fn bfs_limited<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+    max_depth: usize,
+) -> SearchResult<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new();
+    let mut queue: VecDeque<usize> = result.insert_starts(starts, 0).into();
+
+    while let Some(i) = queue.pop_front() {
+        let (node, &(_, depth)) = result.nodes.get_index(i).unwrap();
+        if is_goal(node) {
+            result.goal = Some(i);
+            break;
+        }
+        if depth == max_depth {
+            continue;
+        }
+
+        for next in successors(&node.clone()) {
+            if let Entry::Vacant(entry) = result.nodes.entry(next) {
+                queue.push_back(entry.index());
+                entry.insert((i, depth + 1));
+            }
+        }
+    }
+
+    result
+}
+
+/// Dijkstra's algorithm from several starts, stopping at the first node matching `is_goal`.
+/// `successors` yields the neighbours of a node together with the cost of moving there.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::zero(), is_goal)
+}
+
+/// A* search from several starts, stopping at the first node matching `is_goal`.
+/// The `heuristic` must never overestimate the remaining cost to a goal.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = BinaryHeap::new();
+    for i in result.insert_starts(starts, C::zero()) {
+        let node = result.nodes.get_index(i).unwrap().0;
+        queue.push(Reverse((heuristic(node), C::zero(), i)));
+    }
+
+    while let Some(Reverse((_, cost, i))) = queue.pop() {
+        let (node, &(_, best)) = result.nodes.get_index(i).unwrap();
+        // skip stale entries, the node was reached more cheaply in the meantime.
+        if cost > best {
+            continue;
+        }
+        if is_goal(node) {
+            result.goal = Some(i);
+            break;
+        }
+
+        for (next, step) in successors(&node.clone()) {
+            let next_cost = cost + step;
+            let j = match result.nodes.entry(next) {
+                Entry::Vacant(entry) => {
+                    let j = entry.index();
+                    entry.insert((i, next_cost));
+                    j
+                }
+                Entry::Occupied(mut entry) => {
+                    if entry.get().1 <= next_cost {
+                        continue;
+                    }
+                    entry.insert((i, next_cost));
+                    entry.index()
+                }
+            };
+            let next = result.nodes.get_index(j).unwrap().0;
+            queue.push(Reverse((next_cost + heuristic(next), next_cost, j)));
+        }
+    }
+
+    result
+}
+
+/// Dijkstra's algorithm with a bucket queue, for small non-negative integer costs.
+/// Faster than [`dijkstra`] when costs are bounded, like digits on a map.
+pub fn dijkstra_buckets<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    let mut result = SearchResult::new();
+    let mut buckets: Vec<Vec<usize>> = vec![result.insert_starts(starts, 0)];
+    let mut cost = 0;
+
+    while cost < buckets.len() {
+        let Some(i) = buckets[cost].pop() else {
+            cost += 1;
+            continue;
+        };
+
+        let (node, &(_, best)) = result.nodes.get_index(i).unwrap();
+        if cost > best {
+            continue;
+        }
+        if is_goal(node) {
+            result.goal = Some(i);
+            break;
+        }
+
+        for (next, step) in successors(&node.clone()) {
+            let next_cost = cost + step;
+            let j = match result.nodes.entry(next) {
+                Entry::Vacant(entry) => {
+                    let j = entry.index();
+                    entry.insert((i, next_cost));
+                    j
+                }
+                Entry::Occupied(mut entry) => {
+                    if entry.get().1 <= next_cost {
+                        continue;
+                    }
+                    entry.insert((i, next_cost));
+                    entry.index()
+                }
+            };
+            if buckets.len() <= next_cost {
+                buckets.resize_with(next_cost + 1, Vec::new);
+            }
+            buckets[next_cost].push(j);
+        }
+    }
+
+    result
+}
+
+/// Sums the costs along a path, e.g. to check a result. Useful with [`SearchResult::path`].
+pub fn path_cost<N, C: Zero + Add<Output = C>>(path: &[N], mut cost: impl FnMut(&N, &N) -> C) -> C {
+    path.windows(2)
+        .fold(C::zero(), |acc, pair| acc + cost(&pair[0], &pair[1]))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_distances, dijkstra, dijkstra_buckets, path_cost};
+    use crate::grid::Grid;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.#.##.#.
+...#..#G";
+
+    fn maze() -> (Grid<char>, (usize, usize), (usize, usize)) {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(&'S').unwrap();
+        let goal = grid.find(&'G').unwrap();
+        (grid, start, goal)
+    }
+
+    fn open_neighbors(grid: &Grid<char>, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        grid.neighbors4(pos).filter(|&n| grid[n] != '#').collect()
+    }
+
+    /// A weighted graph with a cheap detour: 0 -> 1 -> 2 -> 3 costs 3, 0 -> 3 costs 10.
+    fn weighted(n: &u32) -> Vec<(u32, u32)> {
+        match n {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn finds_shortest_path_with_bfs() {
+        let (grid, start, goal) = maze();
+        let result = bfs([start], |&p| open_neighbors(&grid, p), |&p| p == goal);
+        assert_eq!(result.cost(), Some(15));
+        assert_eq!(result.goal(), Some(&goal));
+
+        let path = result.path().unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.iter().all(|&p| grid[p] != '#'));
+    }
+
+    #[test]
+    fn reports_unreachable_goal() {
+        let result = bfs([0], |&n: &u32| (n < 5).then_some(n + 1), |&n| n == 10);
+        assert_eq!(result.goal(), None);
+        assert_eq!(result.cost(), None);
+        assert_eq!(result.path(), None);
+        assert_eq!(result.len(), 6);
+    }
+
+    #[test]
+    fn builds_distance_maps() {
+        let (grid, start, _) = maze();
+        let result = bfs_distances([start], |&p| open_neighbors(&grid, p), 3);
+        assert_eq!(result.distance(&start), Some(0));
+        assert_eq!(result.distance(&(0, 3)), Some(3));
+        assert_eq!(result.distance(&(0, 4)), None);
+        assert!(result.distances().all(|(_, d)| d <= 3));
+        assert_eq!(result.path_to(&(1, 2)).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn searches_from_multiple_starts() {
+        let result = bfs_distances([0, 10], |&n: &i32| [n - 1, n + 1], 2);
+        assert_eq!(result.distance(&5), None);
+        assert_eq!(result.distance(&8), Some(2));
+        assert_eq!(result.distance(&-1), Some(1));
+        assert_eq!(result.path_to(&12), Some(vec![10, 11, 12]));
+    }
+
+    #[test]
+    fn finds_cheapest_path_with_dijkstra() {
+        let result = dijkstra([0], weighted, |&n| n == 3);
+        assert_eq!(result.cost(), Some(3));
+        assert_eq!(result.path(), Some(vec![0, 1, 2, 3]));
+
+        let everything = dijkstra([0], weighted, |_| false);
+        assert_eq!(everything.len(), 4);
+        assert_eq!(everything.distance(&3), Some(3));
+    }
+
+    #[test]
+    fn finds_cheapest_path_with_buckets() {
+        let result = dijkstra_buckets(
+            [0],
+            |n| weighted(n).into_iter().map(|(n, c)| (n, c as usize)),
+            |&n| n == 3,
+        );
+        assert_eq!(result.cost(), Some(3));
+        assert_eq!(result.path(), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn agrees_on_the_maze() {
+        let (grid, start, goal) = maze();
+        let weighted = |&p: &(usize, usize)| {
+            open_neighbors(&grid, p)
+                .into_iter()
+                .map(|n| (n, 1usize))
+                .collect::<Vec<_>>()
+        };
+        let manhattan = |&(x, y): &(usize, usize)| goal.0.abs_diff(x) + goal.1.abs_diff(y);
+
+        let expected = Some(15);
+        assert_eq!(dijkstra([start], weighted, |&p| p == goal).cost(), expected);
+        assert_eq!(
+            dijkstra_buckets([start], weighted, |&p| p == goal).cost(),
+            expected
+        );
+
+        let result = astar([start], weighted, manhattan, |&p| p == goal);
+        assert_eq!(result.cost(), expected);
+        assert_eq!(path_cost(&result.path().unwrap(), |_, _| 1), 15);
+        // the heuristic prunes nodes that lead away from the goal.
+        assert!(result.len() < dijkstra([start], weighted, |_| false).len());
+    }
+
+    #[test]
+    fn stops_at_goal_predicate() {
+        let result = dijkstra([1u32], |&n| [(n + 1, 1u32), (n * 3, 1)], |&n| n % 7 == 0);
+        assert_eq!(result.goal(), Some(&7));
+        assert_eq!(result.cost(), Some(3));
+        assert_eq!(path_cost(&result.path().unwrap(), |_, _| 1), 3);
+    }
+}
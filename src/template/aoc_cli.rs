@@ -1,10 +1,13 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    fs,
+    path::Path,
     process::{Command, Output, Stdio},
+    time::Duration,
 };
 
-use crate::Day;
+use crate::{Part, PuzzleId, Year};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -35,77 +38,221 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn download_input(id: PuzzleId) -> Result<Output, AocCommandError> {
+    let input_path = get_input_path(id);
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
-
-    call_aoc_cli(&args)
-}
-
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+    if let Some(parent) = Path::new(&input_path).parent() {
+        fs::create_dir_all(parent).map_err(|_| AocCommandError::IoError)?;
+    }
 
     let args = build_args(
         "download",
         &[
             "--overwrite".into(),
+            "--input-only".into(),
             "--input-file".into(),
             input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
         ],
-        day,
+        id,
     );
 
     let output = call_aoc_cli(&args)?;
-    println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
+/// The response of the puzzle site to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    Incorrect {
+        hint: Option<AnswerHint>,
+        /// How long the site asks us to wait before the next attempt.
+        wait: Option<Duration>,
+    },
+    /// The answer was not checked because we submitted too recently.
+    RateLimited(Duration),
+    /// The part has already been solved.
+    AlreadyCompleted,
+    /// A response we do not know how to interpret.
+    Unknown(String),
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+/// Hint given by the puzzle site for a wrong answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerHint {
+    TooHigh,
+    TooLow,
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+impl SubmitOutcome {
+    /// Interprets the text aoc-cli prints after submitting an answer.
+    #[must_use]
+    pub fn parse(output: &str) -> Self {
+        let text = strip_ansi(output).replace('\n', " ");
+        let lower = text.to_lowercase();
+
+        if lower.contains("that's the right answer") {
+            return Self::Correct;
+        }
+
+        if lower.contains("you gave an answer too recently") {
+            let wait = parse_wait(&lower).unwrap_or(Duration::from_secs(60));
+            return Self::RateLimited(wait);
+        }
+
+        if lower.contains("did you already complete it") {
+            return Self::AlreadyCompleted;
+        }
+
+        if lower.contains("not the right answer") {
+            let hint = if lower.contains("too high") {
+                Some(AnswerHint::TooHigh)
+            } else if lower.contains("too low") {
+                Some(AnswerHint::TooLow)
+            } else {
+                None
+            };
+
+            return Self::Incorrect {
+                hint,
+                wait: parse_wait(&lower),
+            };
+        }
+
+        Self::Unknown(text.trim().to_string())
+    }
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "correct"),
+            SubmitOutcome::Incorrect { hint, wait } => {
+                match hint {
+                    Some(AnswerHint::TooHigh) => write!(f, "incorrect (too high)")?,
+                    Some(AnswerHint::TooLow) => write!(f, "incorrect (too low)")?,
+                    None => write!(f, "incorrect")?,
+                }
+                if let Some(wait) = wait {
+                    write!(f, ", wait {}s", wait.as_secs())?;
+                }
+                Ok(())
+            }
+            SubmitOutcome::RateLimited(wait) => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+            SubmitOutcome::AlreadyCompleted => write!(f, "already completed"),
+            SubmitOutcome::Unknown(_) => write!(f, "unknown response"),
+        }
     }
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
-    let mut cmd_args = args.to_vec();
+/// Extracts a wait time from messages like "please wait 5 minutes" or "You have 4m 31s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some(idx) = text.rfind("you have ") {
+        let rest = &text[idx + "you have ".len()..];
+        if let Some(end) = rest.find(" left to wait") {
+            let mut secs = 0;
+            for token in rest[..end].split_whitespace() {
+                let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                secs += match unit {
+                    "h" => value * 3600,
+                    "m" => value * 60,
+                    "s" => value,
+                    _ => return None,
+                };
+            }
+            return Some(Duration::from_secs(secs));
+        }
+    }
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+    let idx = text.find("please wait ")?;
+    let mut tokens = text[idx + "please wait ".len()..].split_whitespace();
+    let amount = match tokens.next()? {
+        "one" | "a" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    match tokens.next()? {
+        u if u.starts_with("minute") => Some(Duration::from_secs(amount * 60)),
+        u if u.starts_with("second") => Some(Duration::from_secs(amount)),
+        u if u.starts_with("hour") => Some(Duration::from_secs(amount * 3600)),
+        _ => None,
     }
+}
 
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the control sequence up to and including its final byte.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+pub fn submit(id: PuzzleId, part: Part, result: &str) -> Result<SubmitOutcome, AocCommandError> {
+    // workaround: the argument order is inverted for submit.
+    let mut args = build_args("submit", &[], id);
+    args.push(part.to_string());
+    args.push(result.to_string());
+    let output = call_aoc_cli_captured(&args)?;
+
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    Ok(SubmitOutcome::parse(&text))
+}
+
+/// Fetches the event calendar, which shows the stars collected per day.
+/// Goes straight to the site, use [`crate::template::cache::calendar`] instead.
+pub fn calendar(year: Year) -> Result<String, AocCommandError> {
+    let args = ["--year".to_string(), year.to_string(), "calendar".into()];
+
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    if output.status.success() {
+        Ok(strip_ansi(&String::from_utf8_lossy(&output.stdout)))
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
+
+#[must_use]
+pub fn get_input_path(id: PuzzleId) -> String {
+    format!("data/{}/inputs/{}.txt", id.year, id.day)
+}
+
+fn build_args(command: &str, args: &[String], id: PuzzleId) -> Vec<String> {
+    let mut cmd_args = args.to_vec();
+
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        id.year.to_string(),
+        "--day".into(),
+        id.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
@@ -125,3 +272,93 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+/// Like [`call_aoc_cli`], but captures the output so it can be inspected. The output is echoed afterwards.
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    let output = Command::new("aoc")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{AnswerHint, SubmitOutcome};
+
+    #[test]
+    fn parses_correct() {
+        let outcome = SubmitOutcome::parse(
+            "\x1b[32mThat's the right answer! You are one gold star closer to restoring snow operations.\x1b[0m",
+        );
+        assert_eq!(outcome, SubmitOutcome::Correct);
+    }
+
+    #[test]
+    fn parses_too_high_with_wait() {
+        let outcome = SubmitOutcome::parse(
+            "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data; there are also some general tips on the about page, or you can ask for hints on the subreddit. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.",
+        );
+        assert_eq!(
+            outcome,
+            SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooHigh),
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+    }
+
+    #[test]
+    fn parses_too_low() {
+        let outcome = SubmitOutcome::parse(
+            "That's not the right answer; your answer is too low. Please wait one minute before trying again.",
+        );
+        assert_eq!(
+            outcome,
+            SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooLow),
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+    }
+
+    #[test]
+    fn parses_rate_limit() {
+        let outcome = SubmitOutcome::parse(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 4m 31s left to wait.",
+        );
+        assert_eq!(
+            outcome,
+            SubmitOutcome::RateLimited(Duration::from_secs(271))
+        );
+    }
+
+    #[test]
+    fn parses_already_completed() {
+        let outcome = SubmitOutcome::parse(
+            "You don't seem to be solving the right level. Did you already complete it?",
+        );
+        assert_eq!(outcome, SubmitOutcome::AlreadyCompleted);
+    }
+
+    #[test]
+    fn parses_unknown() {
+        let outcome = SubmitOutcome::parse("something else entirely");
+        assert_eq!(
+            outcome,
+            SubmitOutcome::Unknown("something else entirely".into())
+        );
+    }
+}
@@ -0,0 +1,331 @@
+/// Module that routes every request to the puzzle site through a local cache.
+/// Metadata about previous requests is kept on disk, and consecutive requests are spaced out
+/// by a minimum interval so that scripts can not hammer the site.
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path, thread, time::Duration};
+
+use crate::template::aoc_cli::{self, AocCommandError, SubmitOutcome};
+use crate::template::puzzle;
+use crate::template::site::{self, SiteError};
+use crate::template::submissions::{unix_now, History};
+use crate::{Part, PuzzleId, Year};
+
+/// Minimum time between two requests to the puzzle site.
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a fetched calendar is reused before it is fetched again.
+pub const CALENDAR_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+const METADATA_PATH: &str = "data/.cache/requests.txt";
+const LAST_REQUEST_KEY: &str = "last_request";
+
+#[derive(Debug)]
+pub enum Error {
+    Aoc(AocCommandError),
+    Site(SiteError),
+    IO(io::Error),
+}
+
+impl Error {
+    /// Whether the request may succeed when retried. Failed `aoc` and `curl` runs are network
+    /// failures or pages that are not unlocked yet, while missing tools, a missing session or
+    /// local file errors stay the same on every attempt.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Error::Aoc(AocCommandError::BadExitStatus(_))
+                | Error::Site(SiteError::BadExitStatus(_))
+        )
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Aoc(e) => write!(f, "{e}"),
+            Error::Site(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::Aoc(e)
+    }
+}
+
+impl From<SiteError> for Error {
+    fn from(e: SiteError) -> Self {
+        Error::Site(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// A remote resource that is fetched from the puzzle site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Input(PuzzleId),
+    Puzzle(PuzzleId),
+    Calendar(Year),
+}
+
+impl Display for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Resource::Input(id) => write!(f, "input/{id}"),
+            Resource::Puzzle(id) => write!(f, "puzzle/{id}"),
+            Resource::Calendar(year) => write!(f, "calendar/{year}"),
+        }
+    }
+}
+
+/// On-disk metadata: when each resource was last fetched and when the last request was made.
+#[derive(Debug, Default)]
+pub struct Cache {
+    entries: BTreeMap<String, u64>,
+}
+
+impl Cache {
+    pub fn load() -> Result<Self, io::Error> {
+        match fs::read_to_string(METADATA_PATH) {
+            Ok(contents) => Ok(Self::parse(&contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[must_use]
+    pub fn parse(contents: &str) -> Self {
+        let entries = contents
+            .lines()
+            .filter_map(|line| {
+                let (key, timestamp) = line.split_once('\t')?;
+                Some((key.to_string(), timestamp.parse().ok()?))
+            })
+            .collect();
+        Self { entries }
+    }
+
+    fn save(&self) -> Result<(), io::Error> {
+        if let Some(parent) = Path::new(METADATA_PATH).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents: String = self
+            .entries
+            .iter()
+            .map(|(key, timestamp)| format!("{key}\t{timestamp}\n"))
+            .collect();
+        fs::write(METADATA_PATH, contents)
+    }
+
+    /// When a resource was last fetched (in seconds since the unix epoch).
+    #[must_use]
+    pub fn fetched_at(&self, resource: Resource) -> Option<u64> {
+        self.entries.get(&resource.to_string()).copied()
+    }
+
+    /// Whether `resource` was fetched less than `max_age` before `now`.
+    #[must_use]
+    pub fn is_fresh(&self, resource: Resource, max_age: Duration, now: u64) -> bool {
+        self.fetched_at(resource)
+            .is_some_and(|fetched_at| now.saturating_sub(fetched_at) < max_age.as_secs())
+    }
+
+    /// How long to wait at `now` before the next request may be made.
+    #[must_use]
+    pub fn throttle_delay(&self, now: u64) -> Duration {
+        let Some(last) = self.entries.get(LAST_REQUEST_KEY) else {
+            return Duration::ZERO;
+        };
+        let next = last + MIN_REQUEST_INTERVAL.as_secs();
+        Duration::from_secs(next.saturating_sub(now))
+    }
+
+    /// Runs a request against the puzzle site once the minimum interval has passed.
+    /// The request time is recorded, as is the fetch time of `resource` if the request succeeds.
+    fn request<T, E: Into<Error>>(
+        &mut self,
+        resource: Option<Resource>,
+        f: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, Error> {
+        let delay = self.throttle_delay(unix_now());
+        if !delay.is_zero() {
+            println!(
+                "Throttling: waiting {}s before the next request...",
+                delay.as_secs()
+            );
+            thread::sleep(delay);
+        }
+
+        let result = f().map_err(Into::into);
+
+        let now = unix_now();
+        self.entries.insert(LAST_REQUEST_KEY.into(), now);
+        if let (Ok(_), Some(resource)) = (&result, resource) {
+            self.entries.insert(resource.to_string(), now);
+        }
+        // the result of a request that went through must reach the caller, e.g. to record a submission.
+        if let Err(e) = self.save() {
+            eprintln!("Failed to update the request cache: {e}");
+        }
+
+        result
+    }
+}
+
+/// An input is never re-downloaded once it is on disk. Scaffolded (empty) files do not count.
+#[must_use]
+pub fn has_input(id: PuzzleId) -> bool {
+    fs::metadata(aoc_cli::get_input_path(id)).is_ok_and(|m| m.len() > 0)
+}
+
+/// A puzzle page is only re-fetched when it is missing, or when part one has been solved
+/// since it was last fetched and the stored page does not contain part two yet.
+#[must_use]
+pub fn puzzle_needs_refresh(cache: &Cache, history: &History, id: PuzzleId) -> bool {
+    let Ok(html) = fs::read_to_string(puzzle::get_html_path(id)) else {
+        return true;
+    };
+
+    if puzzle::part_count(&html) == 0 {
+        return true;
+    }
+
+    if puzzle::part_count(&html) >= 2 {
+        return false;
+    }
+
+    let fetched_at = cache.fetched_at(Resource::Puzzle(id)).unwrap_or(0);
+    history.submissions().iter().any(|s| {
+        s.part == Part::One && s.outcome == SubmitOutcome::Correct && s.timestamp >= fetched_at
+    })
+}
+
+/// Fetches the puzzle page and stores it next to its Markdown rendering.
+fn fetch_puzzle(cache: &mut Cache, id: PuzzleId) -> Result<(), Error> {
+    let html = cache.request(Some(Resource::Puzzle(id)), || site::fetch_puzzle_html(id))?;
+
+    let html_path = puzzle::get_html_path(id);
+    let markdown_path = puzzle::get_markdown_path(id);
+    if let Some(parent) = Path::new(&html_path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&html_path, &html)?;
+    fs::write(&markdown_path, puzzle::to_markdown(&html))?;
+
+    println!("🎄 Successfully wrote puzzle to \"{markdown_path}\".");
+    Ok(())
+}
+
+/// Downloads input and puzzle description for a puzzle, skipping whatever is already cached.
+pub fn download(id: PuzzleId, force: bool) -> Result<(), Error> {
+    let mut cache = Cache::load()?;
+    let history = History::load(id)?;
+
+    if force || !has_input(id) {
+        cache.request(Some(Resource::Input(id)), || aoc_cli::download_input(id))?;
+    } else {
+        println!("🎄 Input for {id} is already present, skipping download.");
+    }
+
+    if force || puzzle_needs_refresh(&cache, &history, id) {
+        fetch_puzzle(&mut cache, id)?;
+    } else {
+        println!("🎄 Puzzle for {id} is up to date, skipping download.");
+    }
+
+    Ok(())
+}
+
+/// Returns the puzzle page HTML for a puzzle, fetching it first if the cached copy is stale.
+pub fn read(id: PuzzleId) -> Result<String, Error> {
+    let mut cache = Cache::load()?;
+    let history = History::load(id)?;
+
+    if puzzle_needs_refresh(&cache, &history, id) {
+        fetch_puzzle(&mut cache, id)?;
+    }
+
+    Ok(fs::read_to_string(puzzle::get_html_path(id))?)
+}
+
+#[must_use]
+pub fn get_calendar_path(year: Year) -> String {
+    format!("data/.cache/calendar-{year}.txt")
+}
+
+/// Returns the event calendar, fetching it at most once every [`CALENDAR_MAX_AGE`].
+pub fn calendar(year: Year) -> Result<String, Error> {
+    let mut cache = Cache::load()?;
+    let path = get_calendar_path(year);
+
+    if cache.is_fresh(Resource::Calendar(year), CALENDAR_MAX_AGE, unix_now()) {
+        if let Ok(calendar) = fs::read_to_string(&path) {
+            return Ok(calendar);
+        }
+    }
+
+    let calendar = cache.request(Some(Resource::Calendar(year)), || aoc_cli::calendar(year))?;
+    fs::write(&path, &calendar)?;
+    Ok(calendar)
+}
+
+/// Submits an answer. Submissions are never cached, but they are throttled like every other request.
+pub fn submit(id: PuzzleId, part: Part, answer: &str) -> Result<SubmitOutcome, Error> {
+    let mut cache = Cache::load()?;
+    cache.request(None, || aoc_cli::submit(id, part, answer))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Cache, Resource, CALENDAR_MAX_AGE, MIN_REQUEST_INTERVAL};
+    use crate::{puzzle, year};
+
+    #[test]
+    fn parses_metadata() {
+        let cache = Cache::parse("input/2023-01\t100\npuzzle/2023-01\t200\nlast_request\t200\n");
+        assert_eq!(
+            cache.fetched_at(Resource::Input(puzzle!(2023, 1))),
+            Some(100)
+        );
+        assert_eq!(
+            cache.fetched_at(Resource::Puzzle(puzzle!(2023, 1))),
+            Some(200)
+        );
+        assert_eq!(cache.fetched_at(Resource::Input(puzzle!(2023, 2))), None);
+    }
+
+    #[test]
+    fn throttles_requests() {
+        let cache = Cache::parse("last_request\t200\n");
+        assert_eq!(cache.throttle_delay(200), MIN_REQUEST_INTERVAL);
+        assert_eq!(cache.throttle_delay(203), Duration::from_secs(2));
+        assert_eq!(cache.throttle_delay(1000), Duration::ZERO);
+    }
+
+    #[test]
+    fn expires_calendars() {
+        let cache = Cache::parse("calendar/2023\t1000\n");
+        let calendar = Resource::Calendar(year!(2023));
+        let max_age = CALENDAR_MAX_AGE.as_secs();
+        assert!(cache.is_fresh(calendar, CALENDAR_MAX_AGE, 1000));
+        assert!(cache.is_fresh(calendar, CALENDAR_MAX_AGE, 1000 + max_age - 1));
+        assert!(!cache.is_fresh(calendar, CALENDAR_MAX_AGE, 1000 + max_age));
+        let other = Resource::Calendar(year!(2022));
+        assert!(!cache.is_fresh(other, CALENDAR_MAX_AGE, 1000));
+    }
+
+    #[test]
+    fn does_not_throttle_first_request() {
+        let cache = Cache::default();
+        assert_eq!(cache.throttle_delay(0), Duration::ZERO);
+    }
+}
@@ -4,12 +4,14 @@ use crate::template::{
     readme_benchmarks::{self, Timings},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{all_days, PuzzleId, Year};
 
-pub fn handle(is_release: bool, is_timed: bool) {
+pub fn handle(year: Year, is_release: bool, is_timed: bool) {
     let mut timings: Vec<Timings> = vec![];
 
     all_days().for_each(|day| {
+        let id = PuzzleId::new(year, day);
+
         if day > 1 {
             println!();
         }
@@ -17,12 +19,12 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(id, is_timed, is_release).unwrap();
 
         if output.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            let val = child_commands::parse_exec_time(&output, id);
             timings.push(val);
         }
     });
@@ -57,15 +59,15 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(id: PuzzleId) -> String {
+    format!("./src/bin/{id}.rs")
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::Day;
+    use crate::PuzzleId;
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -73,15 +75,19 @@ mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given puzzle
+    pub fn run_solution(
+        id: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&get_path_for_bin(id)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = id.to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
@@ -125,9 +131,9 @@ mod child_commands {
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
+    pub fn parse_exec_time(output: &[String], id: PuzzleId) -> super::Timings {
         let mut timings = super::Timings {
-            day,
+            id,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
@@ -204,7 +210,7 @@ mod child_commands {
     mod tests {
         use super::parse_exec_time;
 
-        use crate::day;
+        use crate::puzzle;
 
         #[test]
         fn test_well_formed() {
@@ -214,7 +220,7 @@ mod child_commands {
                     "Part 2: 10 (74.13ms @ 99999 samples)".into(),
                     "".into(),
                 ],
-                day!(1),
+                puzzle!(2023, 1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
@@ -229,13 +235,28 @@ mod child_commands {
                     "Part 2: 10s (100ms @ 1 samples)".into(),
                     "".into(),
                 ],
-                day!(1),
+                puzzle!(2023, 1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
             assert_eq!(res.part_1.unwrap(), "2s");
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn test_memo_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 21 (1.5ms @ 667 samples) [97.9% memo hits of 4002 lookups]".into(),
+                    "Part 2: ✖        ".into(),
+                    "".into(),
+                ],
+                puzzle!(2023, 12),
+            );
+            assert_approx_eq!(res.total_nanos, 1500000_f64);
+            assert_eq!(res.part_1.unwrap(), "1.5ms");
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
         #[test]
         fn test_missing_parts() {
             let res = parse_exec_time(
@@ -244,7 +265,7 @@ mod child_commands {
                     "Part 2: ✖        ".into(),
                     "".into(),
                 ],
-                day!(1),
+                puzzle!(2023, 1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
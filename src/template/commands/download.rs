@@ -1,15 +1,15 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::{aoc_cli, cache};
+use crate::PuzzleId;
 use std::process;
 
-pub fn handle(day: Day) {
+pub fn handle(id: PuzzleId, force: bool) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = cache::download(id, force) {
+        eprintln!("failed to download: {e}");
         process::exit(1);
     };
 }
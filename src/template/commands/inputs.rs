@@ -0,0 +1,30 @@
+use std::process;
+
+use crate::template::inputs::{self, InputsError};
+use crate::{PuzzleId, Year};
+
+fn report(action: &str, result: Result<Vec<PuzzleId>, InputsError>) {
+    match result {
+        Ok(ids) if ids.is_empty() => println!("🎄 No inputs to {action}."),
+        Ok(ids) => {
+            for id in ids {
+                println!("🎄 Successfully {action}ed input for {id}.");
+            }
+        }
+        Err(e) => {
+            eprintln!("failed to {action} inputs: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+pub fn handle(year: Year, action: &str) {
+    match action {
+        "encrypt" => report("encrypt", inputs::encrypt_all(year)),
+        "decrypt" => report("decrypt", inputs::decrypt_all(year)),
+        x => {
+            eprintln!("Unknown inputs action: {x}. Use \"encrypt\" or \"decrypt\".");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use std::process;
+
+use crate::template::migrate;
+use crate::Year;
+
+pub fn handle(year: Year) {
+    match migrate::migrate(year) {
+        Ok(moves) if moves.is_empty() => println!("🎄 Nothing to migrate."),
+        Ok(moves) => {
+            for m in moves {
+                println!("Moved \"{}\" to \"{}\"", m.from.display(), m.to.display());
+            }
+            println!("---");
+            println!("🎄 Successfully migrated to the layout for {year}.");
+        }
+        Err(e) => {
+            eprintln!("failed to migrate: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,5 +1,10 @@
 pub mod all;
 pub mod download;
+pub mod inputs;
+pub mod migrate;
+pub mod progress;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod start;
+pub mod wait;
@@ -0,0 +1,37 @@
+use std::process;
+
+use crate::template::{
+    aoc_cli,
+    progress::{self, Progress},
+};
+use crate::Year;
+
+pub fn handle(year: Year, calendar: bool) {
+    let progress = if calendar {
+        if aoc_cli::check().is_err() {
+            eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+            process::exit(1);
+        }
+        Progress::load_calendar(year).map_err(progress::Error::from)
+    } else {
+        Progress::load_local(year).map_err(progress::Error::from)
+    };
+
+    let progress = match progress {
+        Ok(progress) => progress,
+        Err(e) => {
+            eprintln!("failed to collect stars: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("⭐ {} stars collected.", progress.total());
+
+    match progress::update(&progress, year) {
+        Ok(()) => println!("Successfully updated README with progress."),
+        Err(e) => {
+            eprintln!("Failed to update readme with progress: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,12 +1,17 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::aoc_cli;
+use crate::PuzzleId;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+const MODULE_TEMPLATE: &str = r#"#[allow(unused_imports)]
+use advent_of_code::prelude::*;
+
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
 
 pub fn part_one(input: &str) -> Option<u32> {
     None
@@ -22,13 +27,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
@@ -39,13 +44,21 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
     OpenOptions::new().write(true).create(true).open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+#[must_use]
+pub fn get_module_path(id: PuzzleId) -> String {
+    format!("src/bin/{id}.rs")
+}
+
+pub fn handle(id: PuzzleId) {
+    let input_path = aoc_cli::get_input_path(id);
+    let example_path = format!("data/{}/examples/{}.txt", id.year, id.day);
+    let module_path = get_module_path(id);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -57,7 +70,8 @@ pub fn handle(day: Day) {
 
     match file.write_all(
         MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
+            .replace("YEAR_NUMBER", &id.year.to_string())
+            .replace("DAY_NUMBER", &id.day.into_inner().to_string())
             .as_bytes(),
     ) {
         Ok(()) => {
@@ -90,5 +104,8 @@ pub fn handle(day: Day) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        id.day, id.year
+    );
 }
@@ -1,9 +1,17 @@
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
-use crate::Day;
+use crate::{Part, PuzzleId};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    id: PuzzleId,
+    release: bool,
+    time: bool,
+    trace: bool,
+    viz: Option<PathBuf>,
+    submit_part: Option<Part>,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), id.to_string()];
 
     if release {
         cmd_args.push("--release".to_string());
@@ -20,6 +28,15 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    if trace {
+        cmd_args.push("--trace".to_string());
+    }
+
+    if let Some(dir) = viz {
+        cmd_args.push("--viz".to_string());
+        cmd_args.push(dir.display().to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
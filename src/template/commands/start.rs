@@ -0,0 +1,65 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    path::Path,
+    process, thread,
+    time::Duration,
+};
+
+use crate::template::commands::{read, scaffold};
+use crate::template::{aoc_cli, cache};
+use crate::PuzzleId;
+
+/// Downloads right after unlock may fail while the site is under load, so they are retried.
+const MAX_ATTEMPTS: u32 = 6;
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// A random delay of up to one second, so retries of many users do not line up.
+fn jitter() -> Duration {
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_millis(random % 1000)
+}
+
+fn download_with_retries(id: PuzzleId) -> Result<(), cache::Error> {
+    let mut attempt = 1;
+    loop {
+        match cache::download(id, false) {
+            Ok(()) => return Ok(()),
+            Err(e) if e.is_transient() && attempt < MAX_ATTEMPTS => {
+                let delay = RETRY_BASE_DELAY * 2_u32.pow(attempt - 1) + jitter();
+                eprintln!(
+                    "Download failed ({e}), retrying in {:.1}s...",
+                    delay.as_secs_f64()
+                );
+                thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Scaffolds a puzzle unless it already exists, downloads input and puzzle and prints the puzzle.
+pub fn handle(id: PuzzleId) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    if Path::new(&scaffold::get_module_path(id)).exists() {
+        println!("Module for {id} already exists, skipping scaffold.");
+    } else {
+        scaffold::handle(id);
+    }
+
+    println!("---");
+
+    if let Err(e) = download_with_retries(id) {
+        eprintln!("failed to download: {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+
+    read::handle(id);
+}
@@ -0,0 +1,36 @@
+use std::{
+    io::{self, Write},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::commands::start;
+use crate::template::submissions::unix_now;
+use crate::template::unlock;
+use crate::PuzzleId;
+
+/// Puzzles unlock on a full second of the system clock, which is when the countdown ticks.
+fn until_next_second() -> Duration {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Duration::from_secs(1) - Duration::from_nanos(since_epoch.subsec_nanos().into())
+}
+
+pub fn handle(id: PuzzleId) {
+    let mut stdout = io::stdout();
+
+    while let Some(remaining) = unlock::time_until_unlock(id, unix_now()) {
+        print!(
+            "\r\x1b[2K⏳ {id} unlocks in {}",
+            unlock::format_countdown(remaining)
+        );
+        stdout.flush().ok();
+
+        // wake up on the full second, so the countdown does not drift.
+        thread::sleep(until_next_second());
+    }
+
+    println!("\r\x1b[2K🔓 {id} is unlocked!");
+    start::handle(id);
+}
@@ -0,0 +1,226 @@
+/// Module that keeps puzzle inputs encrypted at rest, so they can be committed without publishing them.
+/// Inputs are encrypted with ChaCha20-Poly1305. The key is read from the `AOC_INPUTS_KEY` environment
+/// variable or the `.inputs.key` file, as 64 hex characters.
+use std::{env, fmt::Display, fs, io};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+use crate::template::aoc_cli;
+use crate::{all_days, PuzzleId, Year};
+
+const KEY_ENV: &str = "AOC_INPUTS_KEY";
+const KEY_FILE: &str = ".inputs.key";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum InputsError {
+    MissingKey,
+    InvalidKey,
+    Decryption,
+    IO(io::Error),
+}
+
+impl Display for InputsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputsError::MissingKey => write!(f, "no key found in {KEY_ENV} or {KEY_FILE}."),
+            InputsError::InvalidKey => write!(f, "the key must be 64 hex characters."),
+            InputsError::Decryption => {
+                write!(
+                    f,
+                    "could not decrypt input, the key is wrong or the file is corrupt."
+                )
+            }
+            InputsError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for InputsError {
+    fn from(e: io::Error) -> Self {
+        InputsError::IO(e)
+    }
+}
+
+#[must_use]
+pub fn get_encrypted_path(id: PuzzleId) -> String {
+    format!("data/{}/inputs/{}.txt.enc", id.year, id.day)
+}
+
+fn parse_key(hex: &str) -> Result<Key, InputsError> {
+    let hex = hex.trim();
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(InputsError::InvalidKey);
+    }
+
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| InputsError::InvalidKey))
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    Ok(*Key::from_slice(&bytes))
+}
+
+/// Loads the key from the environment, falling back to the key file.
+pub fn load_key() -> Result<Key, InputsError> {
+    if let Ok(hex) = env::var(KEY_ENV) {
+        return parse_key(&hex);
+    }
+
+    match fs::read_to_string(KEY_FILE) {
+        Ok(hex) => parse_key(&hex),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(InputsError::MissingKey),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Encrypts an input. The puzzle id is authenticated as well, so inputs can not be swapped between puzzles.
+/// The output is the random nonce followed by the ciphertext.
+#[must_use]
+pub fn encrypt(key: &Key, id: PuzzleId, input: &str) -> Vec<u8> {
+    let cipher = ChaCha20Poly1305::new(key);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let aad = id.to_string();
+
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: input.as_bytes(),
+                aad: aad.as_bytes(),
+            },
+        )
+        .expect("encryption with a valid key does not fail");
+
+    let mut out = nonce.to_vec();
+    out.extend(ciphertext);
+    out
+}
+
+pub fn decrypt(key: &Key, id: PuzzleId, data: &[u8]) -> Result<String, InputsError> {
+    if data.len() < NONCE_LEN {
+        return Err(InputsError::Decryption);
+    }
+
+    let cipher = ChaCha20Poly1305::new(key);
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let aad = id.to_string();
+
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: aad.as_bytes(),
+            },
+        )
+        .map_err(|_| InputsError::Decryption)?;
+
+    String::from_utf8(plaintext).map_err(|_| InputsError::Decryption)
+}
+
+/// Reads and decrypts the encrypted input of a puzzle.
+pub fn read_encrypted(id: PuzzleId) -> Result<String, InputsError> {
+    let key = load_key()?;
+    let data = fs::read(get_encrypted_path(id))?;
+    decrypt(&key, id, &data)
+}
+
+/// Encrypts every non-empty plaintext input of a year. Returns the puzzles that were encrypted.
+pub fn encrypt_all(year: Year) -> Result<Vec<PuzzleId>, InputsError> {
+    let key = load_key()?;
+    let mut encrypted = vec![];
+
+    for id in all_days().map(|day| PuzzleId::new(year, day)) {
+        let input = match fs::read_to_string(aoc_cli::get_input_path(id)) {
+            Ok(input) if !input.is_empty() => input,
+            Ok(_) => continue,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        // encryption uses a random nonce, so only re-encrypt inputs that actually changed.
+        let path = get_encrypted_path(id);
+        if let Ok(data) = fs::read(&path) {
+            if decrypt(&key, id, &data).is_ok_and(|existing| existing == input) {
+                continue;
+            }
+        }
+
+        fs::write(path, encrypt(&key, id, &input))?;
+        encrypted.push(id);
+    }
+
+    Ok(encrypted)
+}
+
+/// Decrypts every encrypted input of a year into its plaintext file. Returns the puzzles that were decrypted.
+pub fn decrypt_all(year: Year) -> Result<Vec<PuzzleId>, InputsError> {
+    let key = load_key()?;
+    let mut decrypted = vec![];
+
+    for id in all_days().map(|day| PuzzleId::new(year, day)) {
+        let data = match fs::read(get_encrypted_path(id)) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        fs::write(aoc_cli::get_input_path(id), decrypt(&key, id, &data)?)?;
+        decrypted.push(id);
+    }
+
+    Ok(decrypted)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decrypt, encrypt, parse_key, InputsError};
+    use crate::puzzle;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn round_trips_input() {
+        let key = parse_key(KEY).unwrap();
+        let data = encrypt(&key, puzzle!(2023, 3), "467..114..\n...*......\n");
+        assert_eq!(
+            decrypt(&key, puzzle!(2023, 3), &data).unwrap(),
+            "467..114..\n...*......\n"
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_key() {
+        let key = parse_key(KEY).unwrap();
+        let other = parse_key(&KEY.replace("1f", "ff")).unwrap();
+        let data = encrypt(&key, puzzle!(2023, 3), "input");
+        assert!(matches!(
+            decrypt(&other, puzzle!(2023, 3), &data),
+            Err(InputsError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn rejects_swapped_puzzles() {
+        let key = parse_key(KEY).unwrap();
+        let data = encrypt(&key, puzzle!(2023, 3), "input");
+        assert!(matches!(
+            decrypt(&key, puzzle!(2023, 4), &data),
+            Err(InputsError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(matches!(parse_key("abc"), Err(InputsError::InvalidKey)));
+        assert!(matches!(
+            parse_key(&KEY.replace("0a", "zz")),
+            Err(InputsError::InvalidKey)
+        ));
+        assert!(parse_key(&format!("{KEY}\n")).is_ok());
+    }
+}
@@ -0,0 +1,185 @@
+/// Module that moves a repository from the flat single-year layout (`data/inputs/01.txt`, `src/bin/01.rs`)
+/// to the year-scoped layout (`data/2023/inputs/01.txt`, `src/bin/2023-01.rs`).
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+use crate::template::commands::scaffold;
+use crate::{Day, PuzzleId, Year};
+
+const DATA_FOLDERS: [&str; 4] = ["inputs", "examples", "puzzles", "submissions"];
+
+/// A file that is moved to its year-scoped location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// Rewrites a solution module written against the flat layout to name its year.
+/// Numbered example files are read with `read_file_variant`, as `read_file_part` takes a [`crate::Part`].
+#[must_use]
+pub fn migrate_solution_source(source: &str, year: Year) -> String {
+    let solution = Regex::new(r"solution!\((\d+)\)").unwrap();
+    let day_const = Regex::new(r"\bDAY\b").unwrap();
+    let file_part =
+        Regex::new(r"\bread_file_part(\(\s*[^,()]+,\s*[^,()]+,\s*\d+\s*,?\s*\))").unwrap();
+
+    let source = solution.replace_all(source, format!("solution!({year}, $1)"));
+    let source = file_part.replace_all(&source, "read_file_variant$1");
+    day_const.replace_all(&source, "PUZZLE").to_string()
+}
+
+/// Parses the day from a flat data file name like `01.txt`, `01-2.txt` or `01.txt.enc`.
+fn parse_day_file(name: &str) -> Option<Day> {
+    let (day, rest) = name.split_at_checked(2)?;
+    if !rest.starts_with(['.', '-']) {
+        return None;
+    }
+    day.parse().ok()
+}
+
+/// Parses the day from a flat solution module name like `01.rs`.
+fn parse_bin_file(name: &str) -> Option<Day> {
+    let day = name.strip_suffix(".rs")?;
+    if day.len() != 2 {
+        return None;
+    }
+    day.parse().ok()
+}
+
+fn list_files(dir: &Path) -> Result<Vec<String>, io::Error> {
+    match fs::read_dir(dir) {
+        Ok(entries) => {
+            let mut names = vec![];
+            for entry in entries {
+                let entry = entry?;
+                if entry.file_type()?.is_file() {
+                    names.push(entry.file_name().to_string_lossy().to_string());
+                }
+            }
+            names.sort();
+            Ok(names)
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Lists the files of the flat layout and where they belong in the year-scoped layout.
+pub fn plan(year: Year) -> Result<Vec<Move>, io::Error> {
+    let mut moves = vec![];
+
+    for folder in DATA_FOLDERS {
+        let dir = Path::new("data").join(folder);
+        for name in list_files(&dir)? {
+            if parse_day_file(&name).is_some() {
+                moves.push(Move {
+                    from: dir.join(&name),
+                    to: Path::new("data")
+                        .join(year.to_string())
+                        .join(folder)
+                        .join(&name),
+                });
+            }
+        }
+    }
+
+    for name in list_files(Path::new("src/bin"))? {
+        if let Some(day) = parse_bin_file(&name) {
+            moves.push(Move {
+                from: Path::new("src/bin").join(&name),
+                to: PathBuf::from(scaffold::get_module_path(PuzzleId::new(year, day))),
+            });
+        }
+    }
+
+    Ok(moves)
+}
+
+/// Moves every file of the flat layout into the year-scoped layout. Solution modules are rewritten on the way.
+/// Nothing is moved if any of the targets already exists.
+pub fn migrate(year: Year) -> Result<Vec<Move>, io::Error> {
+    let moves = plan(year)?;
+
+    if let Some(m) = moves.iter().find(|m| m.to.exists()) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", m.to.display()),
+        ));
+    }
+
+    for m in &moves {
+        if let Some(parent) = m.to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if m.from.extension().is_some_and(|ext| ext == "rs") {
+            let source = fs::read_to_string(&m.from)?;
+            fs::write(&m.to, migrate_solution_source(&source, year))?;
+            fs::remove_file(&m.from)?;
+        } else {
+            fs::rename(&m.from, &m.to)?;
+        }
+    }
+
+    Ok(moves)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{migrate_solution_source, parse_bin_file, parse_day_file};
+    use crate::{day, year};
+
+    #[test]
+    fn rewrites_solution_source() {
+        let source = [
+            "advent_of_code::solution!(7);",
+            "",
+            "const DAYS: u32 = 1;",
+            "",
+            "fn test() {",
+            "    let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));",
+            "    let result = read_file_part(\"examples\", DAY, 2);",
+            "    let result = part_two(&advent_of_code::template::read_file_part(",
+            "        \"examples\", DAY, 3,",
+            "    ));",
+            "}",
+        ]
+        .join("\n");
+
+        let expected = [
+            "advent_of_code::solution!(2023, 7);",
+            "",
+            "const DAYS: u32 = 1;",
+            "",
+            "fn test() {",
+            "    let result = part_one(&advent_of_code::template::read_file(\"examples\", PUZZLE));",
+            "    let result = read_file_variant(\"examples\", PUZZLE, 2);",
+            "    let result = part_two(&advent_of_code::template::read_file_variant(",
+            "        \"examples\", PUZZLE, 3,",
+            "    ));",
+            "}",
+        ]
+        .join("\n");
+
+        assert_eq!(migrate_solution_source(&source, year!(2023)), expected);
+    }
+
+    #[test]
+    fn recognizes_flat_files() {
+        assert_eq!(parse_day_file("01.txt"), Some(day!(1)));
+        assert_eq!(parse_day_file("12-2.txt"), Some(day!(12)));
+        assert_eq!(parse_day_file("25.txt.enc"), Some(day!(25)));
+        assert_eq!(parse_day_file(".keep"), None);
+        assert_eq!(parse_day_file("26.txt"), None);
+        assert_eq!(parse_day_file("2023"), None);
+
+        assert_eq!(parse_bin_file("09.rs"), Some(day!(9)));
+        assert_eq!(parse_bin_file("2023-09.rs"), None);
+        assert_eq!(parse_bin_file("main.rs"), None);
+    }
+}
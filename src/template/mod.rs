@@ -1,48 +1,133 @@
-use crate::Day;
-use std::{env, fs};
+use crate::template::inputs::InputsError;
+use crate::{Part, PuzzleId, Year};
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    thread,
+};
 
 pub mod aoc_cli;
+pub mod cache;
 pub mod commands;
+pub mod inputs;
+pub mod migrate;
+pub mod progress;
+pub mod puzzle;
+pub mod readme;
 pub mod readme_benchmarks;
 pub mod runner;
+pub mod site;
+pub mod submissions;
+pub mod unlock;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// The year used by commands that are not given one explicitly, configured via `AOC_YEAR`.
+#[must_use]
+pub fn get_default_year() -> Option<Year> {
+    env::var("AOC_YEAR").ok()?.parse().ok()
+}
+
+/// Returns whether the solution was run with `--trace`, asking it to print what it simulates.
+#[must_use]
+pub fn is_tracing() -> bool {
+    env::args().any(|arg| arg == "--trace")
+}
+
+/// Returns the directory given with `--viz <dir>`, asking the solution to write pictures of
+/// what it does there.
+#[must_use]
+pub fn viz_dir() -> Option<PathBuf> {
+    let mut args = env::args_os().skip_while(|arg| arg != "--viz");
+    args.next()?;
+    args.next().map(PathBuf::from)
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, id: PuzzleId) -> String {
+    try_read_file(folder, id).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, if it is available.
+/// Inputs that are only present in encrypted form are decrypted if the key is available.
+///
+/// # Panics
+/// Panics if the encrypted input and the key are present but the input cannot be decrypted,
+/// so that a wrong key or a corrupt file does not pass as a missing input.
+#[must_use]
+pub fn try_read_file(folder: &str, id: PuzzleId) -> Option<String> {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = cwd
+        .join("data")
+        .join(id.year.to_string())
+        .join(folder)
+        .join(format!("{}.txt", id.day));
+    match fs::read_to_string(filepath) {
+        Ok(f) => Some(f),
+        Err(_) if folder == "inputs" => match inputs::read_encrypted(id) {
+            Ok(input) => Some(input),
+            Err(InputsError::MissingKey) => None,
+            Err(InputsError::IO(e)) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => panic!("could not read the encrypted input for {id}: {e}"),
+        },
+        Err(_) => None,
+    }
+}
+
+/// Helper function for tests that run against the real input. Reports the test as skipped
+/// and returns [`None`] if the input is not available, e.g. because the key is missing.
+#[must_use]
+pub fn read_input_or_skip(id: PuzzleId) -> Option<String> {
+    let input = try_read_file("inputs", id);
+    if input.is_none() {
+        let thread = thread::current();
+        let test = thread.name().unwrap_or("test");
+        // written to stderr directly, as the test harness captures `eprintln!`.
+        writeln!(
+            io::stderr(),
+            "{test}: skipped, input for {id} is not available"
+        )
+        .ok();
+    }
+    input
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, id: PuzzleId, part: Part) -> String {
+    read_file_variant(folder, id, part.into_inner())
+}
+
+/// Helper function that reads one of several numbered files to string. E.g. like `10-5.txt`.
+/// Useful when a puzzle has more examples than parts.
+#[must_use]
+pub fn read_file_variant(folder: &str, id: PuzzleId, variant: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
+        .join(id.year.to_string())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{variant}.txt", id.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `PUZZLE` and sets up the input and runner for each part.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        /// The current day.
-        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+    ($year:expr, $day:expr) => {
+        /// The current puzzle.
+        const PUZZLE: advent_of_code::PuzzleId = advent_of_code::puzzle!($year, $day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let input = advent_of_code::template::read_file("inputs", PUZZLE);
+            run_part(part_one, &input, PUZZLE, advent_of_code::Part::One);
+            run_part(part_two, &input, PUZZLE, advent_of_code::Part::Two);
         }
     };
 }
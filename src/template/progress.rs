@@ -0,0 +1,298 @@
+/// Module that tracks collected stars and renders them into the readme.
+/// Stars are either derived from local data (submission history and stored puzzle pages)
+/// or read from the event calendar.
+use std::{collections::BTreeMap, fmt::Display, fs, io};
+
+use crate::template::aoc_cli::{AocCommandError, SubmitOutcome};
+use crate::template::submissions::History;
+use crate::template::{cache, puzzle, readme, site};
+use crate::{all_days, Day, Part, PuzzleId, Year};
+
+static MARKER: &str = "<!--- progress table --->";
+
+#[derive(Debug)]
+pub enum Error {
+    Aoc(AocCommandError),
+    Cache(cache::Error),
+    Readme(readme::Error),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Aoc(e) => write!(f, "{e}"),
+            Error::Cache(e) => write!(f, "{e}"),
+            Error::Readme(readme::Error::Parser(e)) => write!(f, "{e}"),
+            Error::Readme(readme::Error::IO(e)) | Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::Aoc(e)
+    }
+}
+
+impl From<cache::Error> for Error {
+    fn from(e: cache::Error) -> Self {
+        Error::Cache(e)
+    }
+}
+
+impl From<readme::Error> for Error {
+    fn from(e: readme::Error) -> Self {
+        Error::Readme(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The stars collected on a single day.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stars {
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+impl Stars {
+    #[must_use]
+    pub fn from_count(count: usize) -> Self {
+        Self {
+            part_1: count >= 1,
+            part_2: count >= 2,
+        }
+    }
+
+    #[must_use]
+    pub fn count(self) -> u32 {
+        u32::from(self.part_1) + u32::from(self.part_2)
+    }
+
+    fn union(self, other: Self) -> Self {
+        Self {
+            part_1: self.part_1 || other.part_1,
+            part_2: self.part_2 || other.part_2,
+        }
+    }
+}
+
+impl Display for Stars {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let star = |solved: bool| if solved { "⭐" } else { "☆" };
+        write!(f, "{}{}", star(self.part_1), star(self.part_2))
+    }
+}
+
+/// Stars per day. Days without any stars may be missing.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Progress {
+    days: BTreeMap<Day, Stars>,
+}
+
+impl Progress {
+    /// Collects stars from the local submission histories and stored puzzle pages.
+    pub fn load_local(year: Year) -> Result<Self, io::Error> {
+        let mut progress = Self::default();
+
+        for day in all_days() {
+            let id = PuzzleId::new(year, day);
+            progress.add(day, Self::stars_from_history(&History::load(id)?));
+
+            match fs::read_to_string(puzzle::get_html_path(id)) {
+                Ok(html) => progress.add(day, Stars::from_count(puzzle::solved_count(&html))),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(progress)
+    }
+
+    /// Fetches the event calendar through the request cache and reads the stars from it.
+    pub fn load_calendar(year: Year) -> Result<Self, cache::Error> {
+        Ok(Self::parse_calendar(&cache::calendar(year)?))
+    }
+
+    #[must_use]
+    pub fn stars_from_history(history: &History) -> Stars {
+        let solved = |part: Part| {
+            history.submissions().iter().any(|s| {
+                s.part == part
+                    && matches!(
+                        s.outcome,
+                        SubmitOutcome::Correct | SubmitOutcome::AlreadyCompleted
+                    )
+            })
+        };
+
+        Stars {
+            part_1: solved(Part::One),
+            part_2: solved(Part::Two),
+        }
+    }
+
+    /// Parses the calendar as printed by aoc-cli. Every unlocked day is a line of ascii art
+    /// that ends with the day number, followed by one or two `*` for each collected star.
+    #[must_use]
+    pub fn parse_calendar(calendar: &str) -> Self {
+        let mut progress = Self::default();
+
+        for line in calendar.lines() {
+            let line = line.trim_end();
+            let rest = line.trim_end_matches('*');
+            let count = line.len() - rest.len();
+
+            let Some((_, number)) = rest.trim_end().rsplit_once(' ') else {
+                continue;
+            };
+            if count > 2 || (count > 0 && !rest.ends_with(' ')) {
+                continue;
+            }
+
+            if let Some(day) = number.parse().ok().and_then(Day::new) {
+                progress.add(day, Stars::from_count(count));
+            }
+        }
+
+        progress
+    }
+
+    fn add(&mut self, day: Day, stars: Stars) {
+        let entry = self.days.entry(day).or_default();
+        *entry = entry.union(stars);
+    }
+
+    #[must_use]
+    pub fn stars(&self, day: Day) -> Stars {
+        self.days.get(&day).copied().unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn total(&self) -> u32 {
+        self.days.values().map(|s| s.count()).sum()
+    }
+}
+
+fn get_day_link(year: Year, day: Day) -> String {
+    format!(
+        "[{}]({})",
+        day.into_inner(),
+        site::get_puzzle_url(PuzzleId::new(year, day))
+    )
+}
+
+fn construct_calendar(progress: &Progress, year: Year) -> String {
+    let total = progress.total();
+    let max = all_days().count() * 2;
+
+    let mut lines: Vec<String> = vec![
+        "## Progress".into(),
+        String::new(),
+        format!("![Stars](https://img.shields.io/badge/stars%20⭐-{total}%2F{max}-yellow)"),
+        String::new(),
+        "| | | | | |".into(),
+        "| :---: | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    let days: Vec<Day> = all_days().collect();
+    for week in days.chunks(5) {
+        let cells: Vec<String> = week
+            .iter()
+            .map(|&day| format!("{} {}", get_day_link(year, day), progress.stars(day)))
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &Progress, year: Year) -> Result<(), Error> {
+    let calendar = construct_calendar(progress, year);
+    readme::update_section(s, MARKER, &calendar)?;
+    Ok(())
+}
+
+pub fn update(progress: &Progress, year: Year) -> Result<(), Error> {
+    let mut readme = readme::read()?;
+    update_content(&mut readme, progress, year)?;
+    readme::write(&readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, Progress, Stars, MARKER};
+    use crate::template::submissions::History;
+    use crate::{day, year};
+
+    #[test]
+    fn parses_calendar() {
+        let calendar = [
+            "         ~    ~  ~      ~     ~ ~   ~     ~  ~  ~   ~        25",
+            ".--'~ ~ ~|        .-' *       \\  /     '-.   3",
+            "'..'.'~~'|  .'. ' .        '. .'.    ~  .'.  2 *",
+            "  '  ..  '  .. . . .       .. .        .  . 1 **",
+            "     10 ** 20",
+        ]
+        .join("\n");
+
+        let progress = Progress::parse_calendar(&calendar);
+        assert_eq!(progress.stars(day!(1)), Stars::from_count(2));
+        assert_eq!(progress.stars(day!(2)), Stars::from_count(1));
+        assert_eq!(progress.stars(day!(3)), Stars::from_count(0));
+        assert_eq!(progress.stars(day!(10)), Stars::from_count(0));
+        assert_eq!(progress.total(), 3);
+    }
+
+    #[test]
+    fn counts_stars_from_history() {
+        let history = History::parse(
+            "100\t1\t5\ttoo_low\t60\n200\t1\t7\tcorrect\t\n300\t2\t9\talready_completed\t\n",
+        );
+        assert_eq!(
+            Progress::stars_from_history(&history),
+            Stars {
+                part_1: true,
+                part_2: true
+            }
+        );
+
+        let history = History::parse("100\t2\t5\ttoo_low\t60\n");
+        assert_eq!(Progress::stars_from_history(&history), Stars::default());
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let mut progress = Progress::parse_calendar("  1 **\n  2 *\n");
+        progress.add(day!(25), Stars::from_count(1));
+
+        let mut s = format!("foo\n{MARKER}{MARKER}\nbar");
+        update_content(&mut s, &progress, year!(2023)).unwrap();
+
+        let expected = [
+            "foo",
+            MARKER,
+            "## Progress",
+            "",
+            "![Stars](https://img.shields.io/badge/stars%20⭐-4%2F50-yellow)",
+            "",
+            "| | | | | |",
+            "| :---: | :---: | :---: | :---: | :---: |",
+            "| [1](https://adventofcode.com/2023/day/1) ⭐⭐ | [2](https://adventofcode.com/2023/day/2) ⭐☆ | [3](https://adventofcode.com/2023/day/3) ☆☆ | [4](https://adventofcode.com/2023/day/4) ☆☆ | [5](https://adventofcode.com/2023/day/5) ☆☆ |",
+            "| [6](https://adventofcode.com/2023/day/6) ☆☆ | [7](https://adventofcode.com/2023/day/7) ☆☆ | [8](https://adventofcode.com/2023/day/8) ☆☆ | [9](https://adventofcode.com/2023/day/9) ☆☆ | [10](https://adventofcode.com/2023/day/10) ☆☆ |",
+            "| [11](https://adventofcode.com/2023/day/11) ☆☆ | [12](https://adventofcode.com/2023/day/12) ☆☆ | [13](https://adventofcode.com/2023/day/13) ☆☆ | [14](https://adventofcode.com/2023/day/14) ☆☆ | [15](https://adventofcode.com/2023/day/15) ☆☆ |",
+            "| [16](https://adventofcode.com/2023/day/16) ☆☆ | [17](https://adventofcode.com/2023/day/17) ☆☆ | [18](https://adventofcode.com/2023/day/18) ☆☆ | [19](https://adventofcode.com/2023/day/19) ☆☆ | [20](https://adventofcode.com/2023/day/20) ☆☆ |",
+            "| [21](https://adventofcode.com/2023/day/21) ☆☆ | [22](https://adventofcode.com/2023/day/22) ☆☆ | [23](https://adventofcode.com/2023/day/23) ☆☆ | [24](https://adventofcode.com/2023/day/24) ☆☆ | [25](https://adventofcode.com/2023/day/25) ⭐☆ |",
+            MARKER,
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,593 @@
+/// Module that renders puzzle descriptions from the puzzle page HTML.
+/// Only the `<article class="day-desc">` elements are rendered, one per unlocked part.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::PuzzleId;
+
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+const ANSI_HIGHLIGHT: &str = "\x1b[1;97m";
+const ANSI_STAR: &str = "\x1b[1;93m";
+const ANSI_CODE: &str = "\x1b[36m";
+
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// Path of the raw puzzle page.
+#[must_use]
+pub fn get_html_path(id: PuzzleId) -> String {
+    format!("data/{}/puzzles/{}.html", id.year, id.day)
+}
+
+/// Path of the rendered puzzle description.
+#[must_use]
+pub fn get_markdown_path(id: PuzzleId) -> String {
+    format!("data/{}/puzzles/{}.md", id.year, id.day)
+}
+
+/// Renders the puzzle description as Markdown.
+#[must_use]
+pub fn to_markdown(html: &str) -> String {
+    render(html, Mode::Markdown)
+}
+
+/// Renders the puzzle description as styled text for the terminal.
+#[must_use]
+pub fn to_terminal(html: &str) -> String {
+    render(html, Mode::Terminal)
+}
+
+/// The number of parts that are contained in the puzzle page.
+#[must_use]
+pub fn part_count(html: &str) -> usize {
+    articles(&parse(html)).len()
+}
+
+/// Counts the parts the page reports as solved. Solved parts are followed by the accepted answer.
+#[must_use]
+pub fn solved_count(html: &str) -> usize {
+    html.matches("Your puzzle answer was").count()
+}
+
+fn render(html: &str, mode: Mode) -> String {
+    let nodes = parse(html);
+
+    let blocks: Vec<String> = articles(&nodes)
+        .into_iter()
+        .flat_map(|article| render_blocks(article.children(), mode, 0))
+        .collect();
+
+    let mut out = blocks.join("\n\n");
+    out.push('\n');
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Markdown,
+    Terminal,
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq, Eq)]
+enum Node {
+    Element {
+        name: String,
+        attrs: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+impl Node {
+    fn name(&self) -> Option<&str> {
+        match self {
+            Node::Element { name, .. } => Some(name),
+            Node::Text(_) => None,
+        }
+    }
+
+    fn attr(&self, key: &str) -> Option<&str> {
+        match self {
+            Node::Element { attrs, .. } => attrs
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str()),
+            Node::Text(_) => None,
+        }
+    }
+
+    fn has_class(&self, class: &str) -> bool {
+        self.attr("class")
+            .is_some_and(|c| c.split_whitespace().any(|c| c == class))
+    }
+
+    fn children(&self) -> &[Node] {
+        match self {
+            Node::Element { children, .. } => children,
+            Node::Text(_) => &[],
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Node::Text(text) => text.clone(),
+            Node::Element { children, .. } => children.iter().map(Node::text).collect(),
+        }
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.children()
+            .iter()
+            .any(|c| c.name() == Some(name) || c.contains(name))
+    }
+}
+
+fn articles(nodes: &[Node]) -> Vec<&Node> {
+    let mut found = vec![];
+    for node in nodes {
+        if node.name() == Some("article") && node.has_class("day-desc") {
+            found.push(node);
+        } else {
+            found.extend(articles(node.children()));
+        }
+    }
+    found
+}
+
+/* -------------------------------------------------------------------------- */
+
+const VOID_ELEMENTS: [&str; 8] = ["br", "hr", "img", "input", "link", "meta", "area", "source"];
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// An element that has been opened but not closed yet: (name, attrs, children).
+type OpenElement = (String, Vec<(String, String)>, Vec<Node>);
+
+/// A forgiving HTML parser that is good enough for the puzzle pages.
+fn parse(html: &str) -> Vec<Node> {
+    let mut stack: Vec<OpenElement> = vec![(String::new(), vec![], vec![])];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        push_text(&mut stack, &rest[..lt]);
+        rest = &rest[lt..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let end = tag.find('>').unwrap_or(tag.len());
+            let name = tag[..end].trim().to_ascii_lowercase();
+            rest = tag.get(end + 1..).unwrap_or("");
+            close_element(&mut stack, &name);
+        } else {
+            let end = rest.find('>').unwrap_or(rest.len());
+            let tag = &rest[1..end];
+            rest = rest.get(end + 1..).unwrap_or("");
+
+            let self_closing = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let (name, attrs) = parse_tag(tag);
+
+            if name.is_empty() {
+                push_text(&mut stack, "<");
+                continue;
+            }
+
+            if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                // skip the content of scripts and styles entirely.
+                let closing = format!("</{name}");
+                rest = rest.find(&closing).map_or("", |idx| {
+                    let after = &rest[idx..];
+                    after.find('>').map_or("", |end| &after[end + 1..])
+                });
+                continue;
+            }
+
+            if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+                let node = Node::Element {
+                    name,
+                    attrs,
+                    children: vec![],
+                };
+                stack.last_mut().unwrap().2.push(node);
+            } else {
+                stack.push((name, attrs, vec![]));
+            }
+        }
+    }
+
+    while stack.len() > 1 {
+        let name = stack.last().unwrap().0.clone();
+        close_element(&mut stack, &name);
+    }
+
+    stack.pop().unwrap().2
+}
+
+fn push_text(stack: &mut [OpenElement], text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let children = &mut stack.last_mut().unwrap().2;
+    let text = decode_entities(text);
+    if let Some(Node::Text(prev)) = children.last_mut() {
+        prev.push_str(&text);
+    } else {
+        children.push(Node::Text(text));
+    }
+}
+
+/// Closes the innermost open element called `name`, implicitly closing everything opened after it.
+/// Stray closing tags are ignored.
+fn close_element(stack: &mut Vec<OpenElement>, name: &str) {
+    let Some(idx) = stack.iter().rposition(|(n, _, _)| n == name) else {
+        return;
+    };
+    if idx == 0 {
+        return;
+    }
+
+    while stack.len() > idx {
+        let (name, attrs, children) = stack.pop().unwrap();
+        stack.last_mut().unwrap().2.push(Node::Element {
+            name,
+            attrs,
+            children,
+        });
+    }
+}
+
+fn parse_tag(tag: &str) -> (String, Vec<(String, String)>) {
+    let tag = tag.trim();
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let name = tag[..name_end].to_ascii_lowercase();
+
+    let mut attrs = vec![];
+    let mut rest = tag[name_end..].trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let value = if let Some(value) = rest.strip_prefix('=') {
+            let value = value.trim_start();
+            let (raw, remainder) = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let inner = &value[1..];
+                    let end = inner.find(quote).unwrap_or(inner.len());
+                    (&inner[..end], inner.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                    (&value[..end], &value[end..])
+                }
+            };
+            rest = remainder.trim_start();
+            decode_entities(raw)
+        } else {
+            String::new()
+        };
+
+        if !key.is_empty() {
+            attrs.push((key, value));
+        }
+    }
+
+    (name, attrs)
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => {
+                    if let Some(hex) = entity
+                        .strip_prefix("#x")
+                        .or_else(|| entity.strip_prefix("#X"))
+                    {
+                        u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                    } else if let Some(dec) = entity.strip_prefix('#') {
+                        dec.parse().ok().and_then(char::from_u32)
+                    } else {
+                        None
+                    }
+                }
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_blocks(nodes: &[Node], mode: Mode, indent: usize) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut inline_nodes: Vec<&Node> = vec![];
+
+    let flush = |inline_nodes: &mut Vec<&Node>, blocks: &mut Vec<String>| {
+        let text: String = inline_nodes
+            .drain(..)
+            .map(|n| render_inline(n, mode, false, ""))
+            .collect();
+        let text = collapse_whitespace(&text);
+        if !text.is_empty() {
+            blocks.push(text);
+        }
+    };
+
+    for node in nodes {
+        match node.name() {
+            Some("h2") => {
+                flush(&mut inline_nodes, &mut blocks);
+                let title = collapse_whitespace(&render_inline_children(node, mode, false, ""));
+                blocks.push(match mode {
+                    Mode::Markdown => format!("## {title}"),
+                    Mode::Terminal => format!("{ANSI_BOLD}{title}{ANSI_RESET}"),
+                });
+            }
+            Some("p") => {
+                flush(&mut inline_nodes, &mut blocks);
+                let text = collapse_whitespace(&render_inline_children(node, mode, false, ""));
+                if !text.is_empty() {
+                    blocks.push(text);
+                }
+            }
+            Some("pre") => {
+                flush(&mut inline_nodes, &mut blocks);
+                blocks.push(render_pre(node, mode));
+            }
+            Some("ul" | "ol") => {
+                flush(&mut inline_nodes, &mut blocks);
+                blocks.push(render_list(node, mode, indent));
+            }
+            _ => inline_nodes.push(node),
+        }
+    }
+
+    flush(&mut inline_nodes, &mut blocks);
+    blocks
+}
+
+fn render_pre(node: &Node, mode: Mode) -> String {
+    match mode {
+        Mode::Markdown => {
+            let text = node.text();
+            format!("```\n{}\n```", text.trim_end_matches('\n'))
+        }
+        Mode::Terminal => {
+            let mut text = render_inline_children(node, mode, true, "");
+            // drop the trailing line break, even if it is followed by a style reset.
+            while let Some(stripped) = text.strip_suffix(ANSI_RESET) {
+                if !stripped.ends_with('\n') {
+                    break;
+                }
+                text = format!("{}{ANSI_RESET}", stripped.trim_end_matches('\n'));
+            }
+            text.trim_end_matches('\n')
+                .lines()
+                .map(|line| format!("    {line}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+}
+
+fn render_list(node: &Node, mode: Mode, indent: usize) -> String {
+    let padding = "  ".repeat(indent);
+    let bullet = match mode {
+        Mode::Markdown => "-",
+        Mode::Terminal => "•",
+    };
+
+    node.children()
+        .iter()
+        .filter(|child| child.name() == Some("li"))
+        .map(|item| {
+            let blocks = render_blocks(item.children(), mode, indent + 1);
+            let mut lines = vec![];
+            for (i, block) in blocks.iter().enumerate() {
+                if i == 0 {
+                    lines.push(format!("{padding}{bullet} {block}"));
+                } else if block.trim_start().starts_with(bullet) {
+                    // nested lists are already indented.
+                    lines.push(block.clone());
+                } else {
+                    lines.push(format!("{padding}  {block}"));
+                }
+            }
+            lines.join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_inline_children(node: &Node, mode: Mode, pre: bool, style: &str) -> String {
+    node.children()
+        .iter()
+        .map(|child| render_inline(child, mode, pre, style))
+        .collect()
+}
+
+/// Renders inline content. `style` holds the ANSI codes that are active in the surrounding text,
+/// they are re-applied after a nested element resets the terminal style.
+fn render_inline(node: &Node, mode: Mode, pre: bool, style: &str) -> String {
+    let Node::Element { name, .. } = node else {
+        let Node::Text(text) = node else {
+            unreachable!()
+        };
+        return match (pre, mode) {
+            (true, _) => text.clone(),
+            (false, Mode::Markdown) => text.replace(char::is_whitespace, " ").replace('*', "\\*"),
+            (false, Mode::Terminal) => text.replace(char::is_whitespace, " "),
+        };
+    };
+
+    let styled = |code: &str, wrap: &str| -> String {
+        match mode {
+            Mode::Markdown => {
+                let inner = render_inline_children(node, mode, pre, style);
+                wrap_markdown(&inner, wrap)
+            }
+            Mode::Terminal => {
+                let nested = format!("{style}{code}");
+                let inner = render_inline_children(node, mode, pre, &nested);
+                format!("{code}{inner}{ANSI_RESET}{style}")
+            }
+        }
+    };
+
+    match name.as_str() {
+        "em" if node.has_class("star") => styled(ANSI_STAR, "**"),
+        "em" | "strong" | "b" | "i" => styled(ANSI_HIGHLIGHT, "*"),
+        "code" if pre => styled(ANSI_CODE, ""),
+        "code" => match mode {
+            Mode::Markdown => {
+                let code = format!("`{}`", node.text().replace(char::is_whitespace, " "));
+                if node.contains("em") {
+                    format!("*{code}*")
+                } else {
+                    code
+                }
+            }
+            Mode::Terminal => styled(ANSI_CODE, ""),
+        },
+        "a" => {
+            let href = node.attr("href").map(absolute_url).unwrap_or_default();
+            match mode {
+                Mode::Markdown => {
+                    let inner = render_inline_children(node, mode, pre, style);
+                    format!("[{}]({href})", inner.trim())
+                }
+                Mode::Terminal => styled(ANSI_UNDERLINE, ""),
+            }
+        }
+        "br" => "\n".into(),
+        _ => render_inline_children(node, mode, pre, style),
+    }
+}
+
+/// Wraps text in Markdown emphasis markers, keeping surrounding whitespace outside of the markers.
+fn wrap_markdown(inner: &str, wrap: &str) -> String {
+    let trimmed = inner.trim();
+    if trimmed.is_empty() || wrap.is_empty() {
+        return inner.to_string();
+    }
+    let leading = &inner[..inner.len() - inner.trim_start().len()];
+    let trailing = &inner[inner.trim_end().len()..];
+    format!("{leading}{wrap}{trimmed}{wrap}{trailing}")
+}
+
+fn absolute_url(href: &str) -> String {
+    if href.starts_with('/') {
+        format!("{BASE_URL}{href}")
+    } else {
+        href.to_string()
+    }
+}
+
+/// Collapses runs of spaces into one, keeping explicit line breaks.
+fn collapse_whitespace(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            line.split(' ')
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, parse, part_count, solved_count, to_markdown, to_terminal, Node};
+
+    const PART_ONE_HTML: &str = include_str!("fixtures/puzzle_part_one.html");
+    const PART_ONE_MD: &str = include_str!("fixtures/puzzle_part_one.md");
+    const BOTH_PARTS_HTML: &str = include_str!("fixtures/puzzle_both_parts.html");
+    const BOTH_PARTS_MD: &str = include_str!("fixtures/puzzle_both_parts.md");
+    const BOTH_PARTS_TERMINAL: &str = include_str!("fixtures/puzzle_both_parts.ansi.txt");
+
+    #[test]
+    fn renders_part_one_markdown() {
+        assert_eq!(to_markdown(PART_ONE_HTML), PART_ONE_MD);
+    }
+
+    #[test]
+    fn renders_both_parts_markdown() {
+        assert_eq!(to_markdown(BOTH_PARTS_HTML), BOTH_PARTS_MD);
+    }
+
+    #[test]
+    fn renders_both_parts_terminal() {
+        // the fixture spells out escape characters as `^[` to keep it readable.
+        let rendered = to_terminal(BOTH_PARTS_HTML).replace('\x1b', "^[");
+        assert_eq!(rendered, BOTH_PARTS_TERMINAL);
+    }
+
+    #[test]
+    fn counts_parts() {
+        assert_eq!(part_count(PART_ONE_HTML), 1);
+        assert_eq!(part_count(BOTH_PARTS_HTML), 2);
+    }
+
+    #[test]
+    fn counts_solved_parts() {
+        assert_eq!(solved_count(PART_ONE_HTML), 0);
+        assert_eq!(solved_count(BOTH_PARTS_HTML), 2);
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("&lt;a&gt; &amp; &quot;b&quot; &#39;c&#x27; &unknown; & d"),
+            "<a> & \"b\" 'c' &unknown; & d"
+        );
+    }
+
+    #[test]
+    fn closes_unbalanced_elements() {
+        let nodes = parse("<p>one <em>two</p><p>three");
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].text(), "one two");
+        assert_eq!(nodes[1].text(), "three");
+        assert!(matches!(&nodes[1], Node::Element { name, .. } if name == "p"));
+    }
+}
@@ -0,0 +1,90 @@
+/// Module that replaces generated sections of the readme.
+/// A section is delimited by two occurrences of a marker comment; everything in between is overwritten.
+use std::{fs, io};
+
+const README_PATH: &str = "README.md";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+pub struct SectionPosition {
+    pos_start: usize,
+    pos_end: usize,
+}
+
+fn locate_section(readme: &str, marker: &str) -> Result<SectionPosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
+
+    if matches.len() > 2 {
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
+    }
+
+    let pos_start = matches.first().map(|m| m.0).ok_or_else(|| {
+        Error::Parser(format!("{marker}: could not find section start position."))
+    })?;
+
+    let pos_end = matches
+        .last()
+        .map(|m| m.0 + m.1.len())
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find section end position.")))?;
+
+    Ok(SectionPosition { pos_start, pos_end })
+}
+
+/// Replaces the section delimited by `marker` with `content`, wrapped in fresh markers.
+pub fn update_section(s: &mut String, marker: &str, content: &str) -> Result<(), Error> {
+    let positions = locate_section(s, marker)?;
+    let section = format!("{marker}\n{content}\n{marker}");
+    s.replace_range(positions.pos_start..positions.pos_end, &section);
+    Ok(())
+}
+
+pub fn read() -> Result<String, io::Error> {
+    Ok(String::from_utf8_lossy(&fs::read(README_PATH)?).to_string())
+}
+
+pub fn write(readme: &str) -> Result<(), io::Error> {
+    fs::write(README_PATH, readme)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::update_section;
+
+    const MARKER: &str = "<!--- test section --->";
+
+    #[test]
+    fn replaces_section_between_markers() {
+        let mut s = format!("foo\n{MARKER}\nold\n{MARKER}\nbar");
+        update_section(&mut s, MARKER, "new").unwrap();
+        assert_eq!(s, format!("foo\n{MARKER}\nnew\n{MARKER}\nbar"));
+    }
+
+    #[test]
+    fn leaves_other_sections_alone() {
+        let other = "<!--- other section --->";
+        let mut s = format!("{other}\nkeep\n{other}\n{MARKER}{MARKER}");
+        update_section(&mut s, MARKER, "new").unwrap();
+        assert_eq!(
+            s,
+            format!("{other}\nkeep\n{other}\n{MARKER}\nnew\n{MARKER}")
+        );
+    }
+
+    #[test]
+    fn errors_if_marker_missing() {
+        let mut s = "# readme".to_string();
+        assert!(update_section(&mut s, MARKER, "new").is_err());
+    }
+}
@@ -1,51 +1,79 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::memo::{self, MemoStats};
+use crate::template::aoc_cli::SubmitOutcome;
+use crate::template::submissions::{self, History, Submission};
+use crate::template::{self, aoc_cli, cache, ANSI_ITALIC, ANSI_RESET};
+use crate::{Part, PuzzleId};
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    id: PuzzleId,
+    part: Part,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, duration, samples, memo_stats) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&duration, samples, memo_stats),
+    );
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(result, id, part);
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Also returns the lookups of the [`memo::Memo`] caches the function dropped.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, MemoStats) {
+    memo::take_stats();
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
+    let memo_stats = memo::take_stats();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if is_benchmarking() {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        (base_time, 1, memo_stats)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, run.2)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Returns whether the solution should be benched, which `--time` asks for. Solutions that trace or
+/// visualize print or write files on every call, so they are only run once and their output is not
+/// averaged into the time.
+fn is_benchmarking() -> bool {
+    env::args().any(|x| x == "--time") && !template::is_tracing() && template::viz_dir().is_none()
+}
+
+/// Memo caches are scoped to a single call, so every sample starts with cold caches.
+/// The memo lookups are averaged over the samples.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128, MemoStats) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -60,6 +88,7 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     );
 
     let mut timers: Vec<Duration> = vec![];
+    let mut memo_stats = MemoStats::default();
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
@@ -67,12 +96,17 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         let timer = Instant::now();
         func(cloned);
         timers.push(timer.elapsed());
+        memo_stats += memo::take_stats();
     }
 
     (
         #[allow(clippy::cast_possible_truncation)]
         Duration::from_nanos(average_duration(&timers) as u64),
         bench_iterations,
+        MemoStats {
+            hits: memo_stats.hits / bench_iterations as u64,
+            misses: memo_stats.misses / bench_iterations as u64,
+        },
     )
 }
 
@@ -84,11 +118,17 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
+fn format_duration(duration: &Duration, samples: u128, memo_stats: MemoStats) -> String {
+    let timing = if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
         format!(" ({duration:.1?} @ {samples} samples)")
+    };
+    // `cargo all` parses the timing up to "samples)", so the hit rate has to come after it.
+    if memo_stats.lookups() > 0 {
+        format!("{timing} [{memo_stats}]")
+    } else {
+        timing
     }
 }
 
@@ -130,11 +170,12 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///  3. the local submission history does not rule out the answer.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    id: PuzzleId,
+    part: Part,
+) -> Option<Result<SubmitOutcome, cache::Error>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -148,7 +189,7 @@ fn submit_result<T: Display>(
 
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
+    let Some(Ok(part_submit)) = args.get(part_index).map(|p| p.parse::<Part>()) else {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
     };
@@ -162,6 +203,33 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    let answer = result.to_string();
+
+    let history = match History::load(id) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read submission history: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(refusal) = history.check(part, &answer, submissions::unix_now()) {
+        eprintln!("Not submitting {answer}: {refusal}");
+        return None;
+    }
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let outcome = cache::submit(id, part, &answer);
+
+    match &outcome {
+        Ok(outcome) => {
+            println!("Submission outcome: {ANSI_BOLD}{outcome}{ANSI_RESET}");
+            if let Err(e) = History::append(id, &Submission::new(part, &answer, outcome.clone())) {
+                eprintln!("Failed to record submission: {e}");
+            }
+        }
+        Err(e) => eprintln!("Failed to submit: {e}"),
+    }
+
+    Some(outcome)
 }
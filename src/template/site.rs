@@ -0,0 +1,95 @@
+/// Wrapper module around `curl` for fetching pages that aoc-cli does not expose, like the raw puzzle HTML.
+/// The session cookie is looked up the same way aoc-cli does it.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+use crate::PuzzleId;
+
+const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/KingOfDog/advent-of-code-2023";
+
+#[derive(Debug)]
+pub enum SiteError {
+    CommandNotFound,
+    MissingSession,
+    BadExitStatus(Output),
+    IoError,
+}
+
+impl Display for SiteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SiteError::CommandNotFound => write!(f, "curl is not present in environment."),
+            SiteError::MissingSession => write!(
+                f,
+                "no session cookie found in ADVENT_OF_CODE_SESSION or ~/.adventofcode.session."
+            ),
+            SiteError::BadExitStatus(_) => write!(f, "curl exited with a non-zero status."),
+            SiteError::IoError => write!(f, "could not write output files to file system."),
+        }
+    }
+}
+
+#[must_use]
+pub fn get_puzzle_url(id: PuzzleId) -> String {
+    format!("{BASE_URL}/{}/day/{}", id.year, id.day.into_inner())
+}
+
+/// Fetches the puzzle page as HTML.
+pub fn fetch_puzzle_html(id: PuzzleId) -> Result<String, SiteError> {
+    fetch(&get_puzzle_url(id))
+}
+
+fn fetch(url: &str) -> Result<String, SiteError> {
+    let session = get_session().ok_or(SiteError::MissingSession)?;
+
+    // the cookie is passed via stdin so it does not show up in the process list.
+    let mut child = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--user-agent",
+            USER_AGENT,
+            "--config",
+            "-",
+            url,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|_| SiteError::CommandNotFound)?;
+
+    child
+        .stdin
+        .take()
+        .ok_or(SiteError::IoError)?
+        .write_all(format!("cookie = \"session={session}\"\n").as_bytes())
+        .map_err(|_| SiteError::IoError)?;
+
+    let output = child.wait_with_output().map_err(|_| SiteError::IoError)?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(SiteError::BadExitStatus(output))
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Some(session.trim().to_string());
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let path = PathBuf::from(home).join(".adventofcode.session");
+    let session = fs::read_to_string(path).ok()?;
+    Some(session.trim().to_string())
+}
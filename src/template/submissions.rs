@@ -0,0 +1,350 @@
+/// Module that keeps a local history of submitted answers.
+/// The history is used to refuse submissions that can not be right before they reach the puzzle site.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::aoc_cli::{AnswerHint, SubmitOutcome};
+use crate::{Part, PuzzleId};
+
+/// The site asks for at least a minute between wrong answers, even if it does not say so.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub part: Part,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+}
+
+impl Submission {
+    #[must_use]
+    pub fn new(part: Part, answer: &str, outcome: SubmitOutcome) -> Self {
+        Self {
+            timestamp: unix_now(),
+            part,
+            answer: answer.to_string(),
+            outcome,
+        }
+    }
+
+    /// The point in time (in seconds since the unix epoch) until which the site will not accept answers.
+    fn cooldown_until(&self) -> Option<u64> {
+        let wait = match &self.outcome {
+            SubmitOutcome::Incorrect { wait, .. } => wait.unwrap_or(DEFAULT_COOLDOWN),
+            SubmitOutcome::RateLimited(wait) => *wait,
+            _ => return None,
+        };
+        Some(self.timestamp + wait.as_secs())
+    }
+
+    fn to_line(&self) -> String {
+        let (kind, wait) = match &self.outcome {
+            SubmitOutcome::Correct => ("correct", None),
+            SubmitOutcome::Incorrect { hint, wait } => (
+                match hint {
+                    Some(AnswerHint::TooHigh) => "too_high",
+                    Some(AnswerHint::TooLow) => "too_low",
+                    None => "incorrect",
+                },
+                *wait,
+            ),
+            SubmitOutcome::RateLimited(wait) => ("rate_limited", Some(*wait)),
+            SubmitOutcome::AlreadyCompleted => ("already_completed", None),
+            SubmitOutcome::Unknown(_) => ("unknown", None),
+        };
+
+        let wait = wait.map(|w| w.as_secs().to_string()).unwrap_or_default();
+        format!(
+            "{}\t{}\t{}\t{}\t{}",
+            self.timestamp, self.part, self.answer, kind, wait
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let timestamp = fields.next()?.parse().ok()?;
+        let part = fields.next()?.parse().ok()?;
+        let answer = fields.next()?.to_string();
+        let kind = fields.next()?;
+        let wait = fields
+            .next()
+            .and_then(|w| w.parse().ok())
+            .map(Duration::from_secs);
+
+        let outcome = match kind {
+            "correct" => SubmitOutcome::Correct,
+            "too_high" => SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooHigh),
+                wait,
+            },
+            "too_low" => SubmitOutcome::Incorrect {
+                hint: Some(AnswerHint::TooLow),
+                wait,
+            },
+            "incorrect" => SubmitOutcome::Incorrect { hint: None, wait },
+            "rate_limited" => SubmitOutcome::RateLimited(wait.unwrap_or(DEFAULT_COOLDOWN)),
+            "already_completed" => SubmitOutcome::AlreadyCompleted,
+            _ => SubmitOutcome::Unknown(String::new()),
+        };
+
+        Some(Self {
+            timestamp,
+            part,
+            answer,
+            outcome,
+        })
+    }
+}
+
+/// Reasons for refusing to submit an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    RepeatedWrongAnswer,
+    OutOfBounds {
+        lower: Option<i128>,
+        upper: Option<i128>,
+    },
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with {answer}.")
+            }
+            Refusal::RepeatedWrongAnswer => write!(f, "this answer was already rejected."),
+            Refusal::OutOfBounds { lower, upper } => {
+                let lower = lower.map_or("-∞".into(), |l| l.to_string());
+                let upper = upper.map_or("∞".into(), |u| u.to_string());
+                write!(
+                    f,
+                    "earlier replies put the answer between {lower} and {upper} (exclusive)."
+                )
+            }
+            Refusal::Cooldown(left) => {
+                write!(f, "the site asked us to wait, {}s left.", left.as_secs())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    /// Loads the history for a day. A missing history file yields an empty history.
+    pub fn load(id: PuzzleId) -> Result<Self, io::Error> {
+        match fs::read_to_string(get_history_path(id)) {
+            Ok(contents) => Ok(Self::parse(&contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[must_use]
+    pub fn parse(contents: &str) -> Self {
+        let submissions = contents.lines().filter_map(Submission::from_line).collect();
+        Self { submissions }
+    }
+
+    /// Appends a submission to the history file of a day.
+    pub fn append(id: PuzzleId, submission: &Submission) -> Result<(), io::Error> {
+        fs::create_dir_all(get_history_dir(id))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(get_history_path(id))?;
+        writeln!(file, "{}", submission.to_line())
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    /// The accepted answer for a part, if any.
+    #[must_use]
+    pub fn correct_answer(&self, part: Part) -> Option<&str> {
+        self.submissions
+            .iter()
+            .find(|s| s.part == part && s.outcome == SubmitOutcome::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Checks whether an answer may be submitted at `now` (seconds since the unix epoch).
+    pub fn check(&self, part: Part, answer: &str, now: u64) -> Result<(), Refusal> {
+        if let Some(correct) = self.correct_answer(part) {
+            return Err(Refusal::AlreadySolved(correct.to_string()));
+        }
+
+        let attempts = self.submissions.iter().filter(|s| s.part == part);
+
+        if attempts
+            .clone()
+            .any(|s| s.answer == answer && matches!(s.outcome, SubmitOutcome::Incorrect { .. }))
+        {
+            return Err(Refusal::RepeatedWrongAnswer);
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |hint: AnswerHint| {
+                attempts.clone().filter_map(move |s| match s.outcome {
+                    SubmitOutcome::Incorrect { hint: Some(h), .. } if h == hint => {
+                        s.answer.parse::<i128>().ok()
+                    }
+                    _ => None,
+                })
+            };
+
+            let lower = bound(AnswerHint::TooLow).max();
+            let upper = bound(AnswerHint::TooHigh).min();
+
+            if lower.is_some_and(|l| value <= l) || upper.is_some_and(|u| value >= u) {
+                return Err(Refusal::OutOfBounds { lower, upper });
+            }
+        }
+
+        let cooldown = self
+            .submissions
+            .iter()
+            .filter_map(Submission::cooldown_until)
+            .max();
+
+        match cooldown {
+            Some(until) if until > now => Err(Refusal::Cooldown(Duration::from_secs(until - now))),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn get_history_dir(id: PuzzleId) -> String {
+    format!("data/{}/submissions", id.year)
+}
+
+fn get_history_path(id: PuzzleId) -> String {
+    format!("{}/{}.txt", get_history_dir(id), id.day)
+}
+
+/// Seconds since the unix epoch.
+#[must_use]
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{History, Refusal, Submission};
+    use crate::template::aoc_cli::{AnswerHint, SubmitOutcome};
+    use crate::Part;
+
+    fn submission(timestamp: u64, part: Part, answer: &str, outcome: SubmitOutcome) -> Submission {
+        Submission {
+            timestamp,
+            part,
+            answer: answer.into(),
+            outcome,
+        }
+    }
+
+    fn mock_history() -> History {
+        History {
+            submissions: vec![
+                submission(
+                    1000,
+                    Part::One,
+                    "500",
+                    SubmitOutcome::Incorrect {
+                        hint: Some(AnswerHint::TooHigh),
+                        wait: None,
+                    },
+                ),
+                submission(
+                    1100,
+                    Part::One,
+                    "100",
+                    SubmitOutcome::Incorrect {
+                        hint: Some(AnswerHint::TooLow),
+                        wait: Some(Duration::from_secs(300)),
+                    },
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips_lines() {
+        let history = mock_history();
+        let contents = history
+            .submissions()
+            .iter()
+            .map(Submission::to_line)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(
+            History::parse(&contents).submissions(),
+            history.submissions()
+        );
+    }
+
+    #[test]
+    fn refuses_repeated_wrong_answer() {
+        let history = mock_history();
+        assert_eq!(
+            history.check(Part::One, "500", 5000),
+            Err(Refusal::RepeatedWrongAnswer)
+        );
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let history = mock_history();
+        let bounds = Err(Refusal::OutOfBounds {
+            lower: Some(100),
+            upper: Some(500),
+        });
+        assert_eq!(history.check(Part::One, "600", 5000), bounds);
+        assert_eq!(history.check(Part::One, "50", 5000), bounds);
+        assert_eq!(history.check(Part::One, "250", 5000), Ok(()));
+    }
+
+    #[test]
+    fn bounds_are_per_part() {
+        let history = mock_history();
+        assert_eq!(history.check(Part::Two, "600", 5000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_during_cooldown() {
+        let history = mock_history();
+        assert_eq!(
+            history.check(Part::One, "250", 1200),
+            Err(Refusal::Cooldown(Duration::from_secs(200)))
+        );
+        assert_eq!(history.check(Part::One, "250", 1400), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut history = mock_history();
+        history
+            .submissions
+            .push(submission(2000, Part::One, "250", SubmitOutcome::Correct));
+        assert_eq!(
+            history.check(Part::One, "251", 5000),
+            Err(Refusal::AlreadySolved("250".into()))
+        );
+    }
+}
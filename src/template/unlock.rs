@@ -0,0 +1,234 @@
+/// Module that works out when puzzles unlock. Puzzles unlock at midnight US Eastern time.
+/// The offset is computed from the system clock with the US daylight saving rule bundled here,
+/// so no timezone database or network access is needed.
+use std::time::Duration;
+
+use crate::template::submissions::unix_now;
+use crate::{Day, PuzzleId, Year};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+const SECONDS_PER_HOUR: i64 = 60 * 60;
+
+/// Eastern Standard Time, UTC-5.
+const EST_OFFSET: i64 = -5 * SECONDS_PER_HOUR;
+/// Eastern Daylight Time, UTC-4.
+const EDT_OFFSET: i64 = -4 * SECONDS_PER_HOUR;
+
+/// Days since the unix epoch for a date of the proleptic gregorian calendar.
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Date of the proleptic gregorian calendar for days since the unix epoch, as (year, month, day).
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    // month and day are in range 1..=12 and 1..=31.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (year, month as u32, day as u32)
+}
+
+/// Day of the week for days since the unix epoch, with 0 being sunday.
+fn weekday(days: i64) -> i64 {
+    (days + 4).rem_euclid(7)
+}
+
+/// Days since the unix epoch of the `n`-th sunday of a month.
+fn nth_sunday(year: i64, month: u32, n: i64) -> i64 {
+    let first = days_from_civil(year, month, 1);
+    let first_sunday = first + (7 - weekday(first)) % 7;
+    first_sunday + (n - 1) * 7
+}
+
+/// The year a unix timestamp falls into (UTC).
+fn year_of(timestamp: i64) -> i64 {
+    // estimate, then correct for the few days where the estimate is off.
+    let mut year = 1970 + timestamp.div_euclid(SECONDS_PER_DAY) * 400 / 146_097;
+    while days_from_civil(year + 1, 1, 1) * SECONDS_PER_DAY <= timestamp {
+        year += 1;
+    }
+    while days_from_civil(year, 1, 1) * SECONDS_PER_DAY > timestamp {
+        year -= 1;
+    }
+    year
+}
+
+/// UTC offset of US Eastern time at a unix timestamp, in seconds.
+/// Daylight saving time starts on the second sunday of march at 2:00 EST
+/// and ends on the first sunday of november at 2:00 EDT.
+#[must_use]
+pub fn eastern_offset(timestamp: i64) -> i64 {
+    let year = year_of(timestamp);
+    let dst_start = nth_sunday(year, 3, 2) * SECONDS_PER_DAY + 2 * SECONDS_PER_HOUR - EST_OFFSET;
+    let dst_end = nth_sunday(year, 11, 1) * SECONDS_PER_DAY + 2 * SECONDS_PER_HOUR - EDT_OFFSET;
+
+    if (dst_start..dst_end).contains(&timestamp) {
+        EDT_OFFSET
+    } else {
+        EST_OFFSET
+    }
+}
+
+/// The unix timestamp at which a puzzle unlocks.
+#[must_use]
+pub fn unlock_time(id: PuzzleId) -> i64 {
+    let year = i64::from(id.year.into_inner());
+    let local_midnight =
+        days_from_civil(year, 12, u32::from(id.day.into_inner())) * SECONDS_PER_DAY;
+    let standard = local_midnight - EST_OFFSET;
+    local_midnight - eastern_offset(standard)
+}
+
+/// The date in US Eastern time at a unix timestamp, as (year, month, day).
+#[must_use]
+pub fn eastern_date(timestamp: i64) -> (i64, u32, u32) {
+    civil_from_days((timestamp + eastern_offset(timestamp)).div_euclid(SECONDS_PER_DAY))
+}
+
+/// The puzzle that unlocked most recently at `now`, or [`None`] if the event is not running.
+#[must_use]
+pub fn active_puzzle(now: u64) -> Option<PuzzleId> {
+    let (year, month, day) = eastern_date(i64::try_from(now).ok()?);
+    if month != 12 {
+        return None;
+    }
+
+    let year = Year::new(u16::try_from(year).ok()?)?;
+    let day = Day::new(u8::try_from(day).ok()?)?;
+    Some(PuzzleId::new(year, day))
+}
+
+/// Returns the puzzle that unlocked most recently according to the system clock,
+/// or [`None`] outside of the event (December 1st to 25th, US Eastern time).
+#[must_use]
+pub fn current_puzzle() -> Option<PuzzleId> {
+    active_puzzle(unix_now())
+}
+
+/// How long to wait at `now` until the puzzle unlocks, or [`None`] if it is already unlocked.
+#[must_use]
+pub fn time_until_unlock(id: PuzzleId, now: u64) -> Option<Duration> {
+    let remaining = unlock_time(id) - i64::try_from(now).ok()?;
+    u64::try_from(remaining)
+        .ok()
+        .filter(|&r| r > 0)
+        .map(Duration::from_secs)
+}
+
+/// Formats a countdown as `hh:mm:ss`, prefixed with the number of days if there are any.
+#[must_use]
+pub fn format_countdown(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) =
+        (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        active_puzzle, civil_from_days, days_from_civil, eastern_offset, format_countdown,
+        time_until_unlock, unlock_time, EDT_OFFSET, EST_OFFSET,
+    };
+    use crate::puzzle;
+
+    // 2023-12-01T05:00:00Z
+    const DAY_1_2023: i64 = 1_701_406_800;
+
+    #[test]
+    fn converts_civil_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2023, 12, 1), 19_692);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+
+        for days in [-1, 0, 11_017, 19_692, 19_783] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+        assert_eq!(civil_from_days(19_692), (2023, 12, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn finds_active_puzzle() {
+        let now = u64::try_from(DAY_1_2023).unwrap();
+        assert_eq!(active_puzzle(now - 1), None);
+        assert_eq!(active_puzzle(now), Some(puzzle!(2023, 1)));
+        // 23:59:59 EST on the first is still day one.
+        assert_eq!(active_puzzle(now + 86399), Some(puzzle!(2023, 1)));
+        assert_eq!(active_puzzle(now + 86400), Some(puzzle!(2023, 2)));
+        assert_eq!(active_puzzle(now + 24 * 86400), Some(puzzle!(2023, 25)));
+        assert_eq!(active_puzzle(now + 25 * 86400), None);
+        // 2024-07-04T12:00:00Z
+        assert_eq!(active_puzzle(1_720_094_400), None);
+    }
+
+    #[test]
+    fn unlocks_at_midnight_eastern() {
+        assert_eq!(unlock_time(puzzle!(2023, 1)), DAY_1_2023);
+        assert_eq!(unlock_time(puzzle!(2023, 25)), DAY_1_2023 + 24 * 86400);
+        // 2015-12-01T05:00:00Z
+        assert_eq!(unlock_time(puzzle!(2015, 1)), 1_448_946_000);
+    }
+
+    #[test]
+    fn applies_daylight_saving_rule() {
+        // 2023-03-12T06:59:59Z is 01:59:59 EST, one second later it is 03:00:00 EDT.
+        assert_eq!(eastern_offset(1_678_604_399), EST_OFFSET);
+        assert_eq!(eastern_offset(1_678_604_400), EDT_OFFSET);
+        // 2023-11-05T05:59:59Z is 01:59:59 EDT, one second later it is 01:00:00 EST.
+        assert_eq!(eastern_offset(1_699_163_999), EDT_OFFSET);
+        assert_eq!(eastern_offset(1_699_164_000), EST_OFFSET);
+        // 2024-07-04T12:00:00Z
+        assert_eq!(eastern_offset(1_720_094_400), EDT_OFFSET);
+        // 2024-01-01T00:00:00Z
+        assert_eq!(eastern_offset(1_704_067_200), EST_OFFSET);
+    }
+
+    #[test]
+    fn computes_remaining_time() {
+        let now = u64::try_from(DAY_1_2023).unwrap();
+        assert_eq!(
+            time_until_unlock(puzzle!(2023, 1), now - 3661),
+            Some(Duration::from_secs(3661))
+        );
+        assert_eq!(
+            time_until_unlock(puzzle!(2023, 2), now),
+            Some(Duration::from_secs(86400))
+        );
+        assert_eq!(time_until_unlock(puzzle!(2023, 1), now), None);
+        assert_eq!(time_until_unlock(puzzle!(2023, 1), now + 1), None);
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(0)), "00:00:00");
+        assert_eq!(format_countdown(Duration::from_secs(3661)), "01:01:01");
+        assert_eq!(format_countdown(Duration::from_secs(90061)), "1d 01:01:01");
+    }
+}
@@ -0,0 +1,770 @@
+//! Pictures of grids, written as PPM, PNG or animated GIF without any system libraries.
+//!
+//! Solutions render a frame with [`Image::from_grid`] or [`Image::from_rows`] and a colour for
+//! every cell, and collect frames in a [`Recorder`]. They only do so when run with
+//! `--viz <dir>`, see [`crate::template::viz_dir`].
+//!
+//! ```
+//! # use advent_of_code::grid::Grid;
+//! # use advent_of_code::viz::{Image, Recorder, Rgb};
+//! let grid: Grid<char> = "#.\n.#".parse().unwrap();
+//! let frame = Image::from_grid(&grid, |&c| if c == '#' { Rgb::WHITE } else { Rgb::BLACK });
+//! assert_eq!(frame.get((1, 1)), Rgb::WHITE);
+//! assert!(frame.to_png().starts_with(b"\x89PNG"));
+//!
+//! let mut recorder = Recorder::new(10);
+//! recorder.push(frame.scaled(4)).unwrap();
+//! assert!(recorder.to_gif().unwrap().starts_with(b"GIF89a"));
+//! ```
+
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use indexmap::IndexSet;
+
+use crate::bitgrid::BitGrid;
+use crate::grid::Grid;
+use crate::prelude::*;
+
+/// A colour with 8 bits per channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(133, 153, 0);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(181, 137, 0);
+
+    /// Blends towards `other`, `t` being the share of `other` between 0 and 1.
+    #[must_use]
+    pub fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let channel =
+            |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        Rgb(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+}
+
+/// A picture in row-major order, with `(x, y)` positions like a [`Grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Creates an image filled with `background`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Renders rows of cells, like a `Vec<Vec<T>>`, one pixel per cell.
+    ///
+    /// # Panics
+    /// Panics if the rows are not all as wide as the first one.
+    #[must_use]
+    pub fn from_rows<T, R: AsRef<[T]>>(rows: &[R], mut colour: impl FnMut(&T) -> Rgb) -> Self {
+        let width = rows.first().map_or(0, |row| row.as_ref().len());
+        let mut pixels = Vec::with_capacity(width * rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            let row = row.as_ref();
+            assert_eq!(row.len(), width, "row {y} is not {width} cells wide");
+            pixels.extend(row.iter().map(&mut colour));
+        }
+        Self {
+            width,
+            height: rows.len(),
+            pixels,
+        }
+    }
+
+    /// Renders a grid, one pixel per cell.
+    #[must_use]
+    pub fn from_grid<T>(grid: &Grid<T>, colour: impl FnMut(&T) -> Rgb) -> Self {
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.iter().map(colour).collect(),
+        }
+    }
+
+    /// Renders a bit grid with one colour for set cells and another for the rest.
+    #[must_use]
+    pub fn from_bits(bits: &BitGrid, on: Rgb, off: Rgb) -> Self {
+        let mut image = Self::new(bits.width(), bits.height(), off);
+        for pos in bits.iter_ones() {
+            image.set(pos, on);
+        }
+        image
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    /// # Panics
+    /// Panics if `pos` is outside the image.
+    #[must_use]
+    pub fn get(&self, (x, y): (usize, usize)) -> Rgb {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the image"
+        );
+        self.pixels[y * self.width + x]
+    }
+
+    /// # Panics
+    /// Panics if `pos` is outside the image.
+    pub fn set(&mut self, (x, y): (usize, usize), colour: Rgb) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the image"
+        );
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    /// Returns the image with every pixel blown up to a `factor` by `factor` square,
+    /// since a pixel per cell is tiny for most puzzles.
+    #[must_use]
+    pub fn scaled(&self, factor: usize) -> Self {
+        let width = self.width * factor;
+        let mut pixels = Vec::with_capacity(width * self.height * factor);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            let wide = row
+                .iter()
+                .flat_map(|&pixel| std::iter::repeat_n(pixel, factor))
+                .collect_vec();
+            for _ in 0..factor {
+                pixels.extend_from_slice(&wide);
+            }
+        }
+        Self {
+            width,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    /// Encodes the image as a binary PPM (P6), the simplest format most viewers open.
+    #[must_use]
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend(self.pixels.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        out
+    }
+
+    /// Encodes the image as an 8-bit RGB PNG. The pixels are stored without compression,
+    /// which keeps the encoder tiny and is fine for puzzle-sized pictures.
+    #[must_use]
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            // filter type 0: the row as is
+            raw.push(0);
+            raw.extend(row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, deflate, no filtering extensions, no interlacing
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_png_chunk(&mut out, b"IHDR", &header);
+        write_png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        write_png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    /// Writes the image as PNG or PPM, chosen by the extension of `path`,
+    /// creating the directories leading up to it.
+    ///
+    /// # Errors
+    /// Returns an error for other extensions or if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), VizError> {
+        let path = path.as_ref();
+        let bytes = match extension(path).as_deref() {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => return Err(VizError::UnsupportedFormat(path.display().to_string())),
+        };
+        write_file(path, &bytes)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Collects frames of equal size for an animation.
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    frames: Vec<Image>,
+    delay: u16,
+}
+
+impl Recorder {
+    /// Creates an empty recorder showing every frame for `delay` hundredths of a second.
+    #[must_use]
+    pub fn new(delay: u16) -> Self {
+        Self {
+            frames: Vec::new(),
+            delay,
+        }
+    }
+
+    /// Adds a frame at the end.
+    ///
+    /// # Errors
+    /// Returns an error if the frame is not the size of the first one.
+    pub fn push(&mut self, frame: Image) -> Result<(), VizError> {
+        if let Some(first) = self.frames.first() {
+            let expected = (first.width, first.height);
+            let found = (frame.width, frame.height);
+            if expected != found {
+                return Err(VizError::SizeMismatch {
+                    frame: self.frames.len(),
+                    expected,
+                    found,
+                });
+            }
+        }
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Encodes the frames as a looping GIF, with one palette shared by all of them.
+    ///
+    /// # Errors
+    /// Returns an error without frames, or if they use more than 256 colours between them.
+    pub fn to_gif(&self) -> Result<Vec<u8>, VizError> {
+        let first = self.frames.first().ok_or(VizError::NoFrames)?;
+        let (width, height) = (gif_size(first.width)?, gif_size(first.height)?);
+
+        let mut palette: IndexSet<Rgb> = IndexSet::new();
+        palette.extend(
+            self.frames
+                .iter()
+                .flat_map(|frame| frame.pixels.iter().copied()),
+        );
+        if palette.len() > 256 {
+            return Err(VizError::TooManyColours(palette.len()));
+        }
+        // the colour table holds 2^bits entries, with at least two of them
+        let bits = (usize::BITS - (palette.len().max(2) - 1).leading_zeros()) as u8;
+
+        let mut out = b"GIF89a".to_vec();
+        out.extend_from_slice(&width.to_le_bytes());
+        out.extend_from_slice(&height.to_le_bytes());
+        // global colour table, 8 bits per channel, unsorted
+        out.extend_from_slice(&[0xf0 | (bits - 1), 0, 0]);
+        for i in 0..1 << bits {
+            let Rgb(r, g, b) = palette.get_index(i).copied().unwrap_or_default();
+            out.extend_from_slice(&[r, g, b]);
+        }
+        // loop forever
+        out.extend_from_slice(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+        for frame in &self.frames {
+            let [lo, hi] = self.delay.to_le_bytes();
+            out.extend_from_slice(&[0x21, 0xf9, 0x04, 0x00, lo, hi, 0x00, 0x00]);
+            out.push(0x2c);
+            out.extend_from_slice(&[0, 0, 0, 0]);
+            out.extend_from_slice(&width.to_le_bytes());
+            out.extend_from_slice(&height.to_le_bytes());
+            out.push(0);
+
+            let indices = frame
+                .pixels
+                .iter()
+                .map(|pixel| palette.get_index_of(pixel).unwrap() as u8)
+                .collect_vec();
+            let min_code_size = bits.max(2);
+            out.push(min_code_size);
+            for block in lzw_encode(&indices, min_code_size).chunks(255) {
+                out.push(block.len() as u8);
+                out.extend_from_slice(block);
+            }
+            out.push(0);
+        }
+
+        out.push(0x3b);
+        Ok(out)
+    }
+
+    /// Writes the animation as a GIF, or the frames as numbered PNG or PPM files
+    /// (`frame-0000.png`, ...) into a directory if `path` has no extension.
+    ///
+    /// # Errors
+    /// Returns an error for other extensions, if the GIF cannot be encoded or
+    /// if a file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), VizError> {
+        let path = path.as_ref();
+        match extension(path).as_deref() {
+            Some("gif") => write_file(path, &self.to_gif()?),
+            None => self
+                .frames
+                .iter()
+                .enumerate()
+                .try_for_each(|(i, frame)| frame.save(path.join(format!("frame-{i:04}.png")))),
+            _ => Err(VizError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+}
+
+fn extension(path: &Path) -> Option<String> {
+    Some(path.extension()?.to_str()?.to_ascii_lowercase())
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), VizError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+fn gif_size(len: usize) -> Result<u16, VizError> {
+    u16::try_from(len).map_err(|_| VizError::TooLarge(len))
+}
+
+/* -------------------------------------------------------------------------- */
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xedb8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+/// The CRC-32 PNG puts after every chunk.
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &byte| {
+        CRC32_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// The Adler-32 checksum at the end of a zlib stream.
+fn adler32(bytes: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (a, b) = bytes.chunks(5552).fold((1, 0), |(mut a, mut b), chunk| {
+        for &byte in chunk {
+            a += u32::from(byte);
+            b += a;
+        }
+        (a % MOD, b % MOD)
+    });
+    b << 16 | a
+}
+
+/// Wraps `data` in a zlib stream of stored deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn write_png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Packs codes of varying width into bytes, least significant bit first, like GIF wants them.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    len: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u32) {
+        self.buffer |= u32::from(code) << self.len;
+        self.len += width;
+        while self.len >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Compresses palette indices with the variable width LZW flavour of GIF.
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODE: u16 = 4095;
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let initial_width = u32::from(min_code_size) + 1;
+
+    let mut out = BitWriter::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::default();
+    let mut width = initial_width;
+    // the code most recently added to the table
+    let mut last = end;
+
+    out.write(clear, width);
+    let Some((&first, rest)) = indices.split_first() else {
+        out.write(end, width);
+        return out.finish();
+    };
+    let mut code = u16::from(first);
+    // the last code is written after the input runs out, with the table advanced like before
+    for index in rest.iter().copied().map(Some).chain([None]) {
+        if let Some(&known) = index.and_then(|index| table.get(&(code, index))) {
+            code = known;
+            continue;
+        }
+        out.write(code, width);
+        last += 1;
+        if last == 1 << width {
+            width += 1;
+        }
+        if last == MAX_CODE {
+            out.write(clear, width);
+            table.clear();
+            width = initial_width;
+            last = end;
+        } else if let Some(index) = index {
+            table.insert((code, index), last);
+        }
+        code = index.map_or(code, u16::from);
+    }
+    out.write(end, width);
+    out.finish()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when writing images and animations.
+#[derive(Debug)]
+pub enum VizError {
+    Io(io::Error),
+    /// The path does not end in an extension the format is known by.
+    UnsupportedFormat(String),
+    NoFrames,
+    SizeMismatch {
+        frame: usize,
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// A GIF has a palette of at most 256 colours.
+    TooManyColours(usize),
+    /// A GIF is at most 65535 pixels wide and high.
+    TooLarge(usize),
+}
+
+impl Display for VizError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VizError::Io(err) => write!(f, "could not write the file: {err}."),
+            VizError::UnsupportedFormat(path) => write!(f, "no known image format for {path:?}."),
+            VizError::NoFrames => write!(f, "the animation has no frames."),
+            VizError::SizeMismatch {
+                frame,
+                expected,
+                found,
+            } => write!(
+                f,
+                "frame {frame} is {}x{}, expected {}x{} like the first frame.",
+                found.0, found.1, expected.0, expected.1
+            ),
+            VizError::TooManyColours(count) => {
+                write!(f, "the frames use {count} colours, a GIF allows 256.")
+            }
+            VizError::TooLarge(len) => write!(f, "{len} pixels is too large for a GIF."),
+        }
+    }
+}
+
+impl Error for VizError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            VizError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for VizError {
+    fn from(err: io::Error) -> Self {
+        VizError::Io(err)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{adler32, crc32, lzw_encode, zlib_stored, Image, Recorder, Rgb, VizError};
+    use crate::bitgrid::BitGrid;
+    use crate::grid::Grid;
+
+    /// A textbook GIF LZW decoder, to check the encoder against.
+    fn lzw_decode(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut width = u32::from(min_code_size) + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear).map(|i| vec![i as u8]).collect();
+            table.extend([vec![], vec![]]);
+        };
+        reset(&mut table);
+
+        let (mut buffer, mut len, mut pos) = (0u32, 0u32, 0);
+        let mut previous: Option<Vec<u8>> = None;
+        let mut out = Vec::new();
+        loop {
+            while len < width {
+                buffer |= u32::from(bytes[pos]) << len;
+                pos += 1;
+                len += 8;
+            }
+            let code = (buffer & ((1 << width) - 1)) as usize;
+            buffer >>= width;
+            len -= width;
+
+            if code == clear {
+                reset(&mut table);
+                width = u32::from(min_code_size) + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => [prev.clone(), vec![prev[0]]].concat(),
+                (None, None) => panic!("unknown first code {code}"),
+            };
+            if let Some(prev) = previous {
+                if table.len() < 4096 {
+                    table.push([prev, vec![entry[0]]].concat());
+                }
+            }
+            if table.len() == 1 << width && width < 12 {
+                width += 1;
+            }
+            out.extend_from_slice(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(adler32(&vec![255; 100_000]), {
+            let (a, b) = (0..100_000u64).fold((1u64, 0u64), |(a, b), _| {
+                let a = (a + 255) % 65521;
+                (a, (b + a) % 65521)
+            });
+            (b << 16 | a) as u32
+        });
+    }
+
+    #[test]
+    fn stores_zlib_blocks() {
+        let data = vec![7; 70_000];
+        let stream = zlib_stored(&data);
+        assert_eq!(&stream[..2], &[0x78, 0x01]);
+        // two stored blocks, only the second one final
+        assert_eq!(stream[2], 0);
+        assert_eq!(&stream[3..7], &[0xff, 0xff, 0x00, 0x00]);
+        assert_eq!(stream[7 + 65535], 1);
+        assert_eq!(stream.len(), 2 + 2 * 5 + data.len() + 4);
+        assert_eq!(zlib_stored(&[]).len(), 2 + 5 + 4);
+    }
+
+    #[test]
+    fn lzw_round_trips() {
+        let repetitive = (0..20_000).map(|i| (i / 7 % 3) as u8).collect::<Vec<_>>();
+        let noisy = (0..20_000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect::<Vec<_>>();
+        for (indices, min_code_size) in [
+            (vec![], 2),
+            (vec![0], 2),
+            (vec![1, 1, 1, 1, 1, 1, 1], 2),
+            (repetitive, 2),
+            (noisy, 8),
+        ] {
+            let encoded = lzw_encode(&indices, min_code_size);
+            assert_eq!(lzw_decode(&encoded, min_code_size), indices);
+        }
+    }
+
+    #[test]
+    fn renders_grids() {
+        let rows = vec![vec![1, 2], vec![3, 4]];
+        let image = Image::from_rows(&rows, |&n| Rgb(n, 0, 0));
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert_eq!(image.get((0, 1)), Rgb(3, 0, 0));
+
+        let grid = Grid::new(2, 2, vec![1, 2, 3, 4]).unwrap();
+        assert_eq!(Image::from_grid(&grid, |&n| Rgb(n, 0, 0)), image);
+
+        let scaled = image.scaled(3);
+        assert_eq!((scaled.width(), scaled.height()), (6, 6));
+        assert_eq!(scaled.get((5, 2)), Rgb(2, 0, 0));
+        assert_eq!(scaled.get((2, 3)), Rgb(3, 0, 0));
+
+        let mut bits = BitGrid::new(2, 1);
+        bits.set((1, 0), true);
+        let image = Image::from_bits(&bits, Rgb::WHITE, Rgb::BLACK);
+        assert_eq!(image.pixels(), &[Rgb::BLACK, Rgb::WHITE]);
+    }
+
+    #[test]
+    fn encodes_ppm_and_png() {
+        let image = Image::from_rows(&[[Rgb(1, 2, 3), Rgb::WHITE]], |&c| c);
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\x01\x02\x03\xff\xff\xff");
+
+        let png = image.to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[8..16], b"\x00\x00\x00\x0dIHDR");
+        assert_eq!(&png[16..29], &[0, 0, 0, 2, 0, 0, 0, 1, 8, 2, 0, 0, 0]);
+        assert_eq!(
+            &png[png.len() - 12..],
+            b"\x00\x00\x00\x00IEND\xae\x42\x60\x82"
+        );
+        let raw = [0, 1, 2, 3, 255, 255, 255];
+        let idat = zlib_stored(&raw);
+        assert_eq!(&png[37..41], b"IDAT");
+        assert_eq!(&png[41..41 + idat.len()], idat.as_slice());
+    }
+
+    #[test]
+    fn encodes_gifs() {
+        let mut recorder = Recorder::new(5);
+        assert!(matches!(recorder.to_gif(), Err(VizError::NoFrames)));
+        for colour in [Rgb::RED, Rgb::GREEN, Rgb::BLUE] {
+            recorder.push(Image::new(3, 2, colour)).unwrap();
+        }
+        assert!(matches!(
+            recorder.push(Image::new(2, 3, Rgb::RED)),
+            Err(VizError::SizeMismatch { frame: 3, .. })
+        ));
+
+        let gif = recorder.to_gif().unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(&gif[6..13], &[3, 0, 2, 0, 0xf1, 0, 0]);
+        // four colours, the last one unused
+        assert_eq!(&gif[13..16], &[220, 50, 47]);
+        assert_eq!(&gif[22..25], &[0, 0, 0]);
+        assert_eq!(&gif[25..28], b"\x21\xff\x0b");
+        assert_eq!(gif.iter().filter(|&&b| b == 0x2c).count(), 3);
+        assert_eq!(gif.last(), Some(&0x3b));
+
+        let colourful = Image::from_rows(&[(0..=256).collect::<Vec<u32>>()], |&n| {
+            Rgb((n % 256) as u8, (n / 256) as u8, 0)
+        });
+        let mut recorder = Recorder::new(5);
+        recorder.push(colourful).unwrap();
+        assert!(matches!(
+            recorder.to_gif(),
+            Err(VizError::TooManyColours(257))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_formats() {
+        let image = Image::new(1, 1, Rgb::BLACK);
+        assert!(matches!(
+            image.save("picture.jpg"),
+            Err(VizError::UnsupportedFormat(_))
+        ));
+        assert!(matches!(
+            Recorder::new(1).save("animation.png"),
+            Err(VizError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn mixes_colours() {
+        assert_eq!(Rgb::BLACK.mix(Rgb::WHITE, 0.5), Rgb(128, 128, 128));
+        assert_eq!(Rgb::BLACK.mix(Rgb::WHITE, 2.0), Rgb::WHITE);
+    }
+}